@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use libfmod::ffi::{
+    FMOD_INIT_NORMAL, FMOD_STUDIO_INIT_NORMAL, FMOD_STUDIO_LOAD_BANK_NORMAL,
+    FMOD_STUDIO_PARAMETER_GLOBAL,
+};
+use libfmod::{EventDescription, OutputType, ParameterDescription, ParameterType, Studio};
+
+use crate::Result;
+use crate::project::{Parameter, Project};
+
+/// Loads the banks into a studio system without audio output and reads their contents.
+pub(crate) fn read(paths: &[impl AsRef<Path>]) -> Result<Project> {
+    let studio = Studio::create()?;
+
+    // The studio has to be released even if it could not be initialized.
+    let project = initialize(&studio).and_then(|()| read_loaded(&studio, paths));
+
+    studio.release()?;
+    project
+}
+
+fn initialize(studio: &Studio) -> Result<()> {
+    studio.get_core_system()?.set_output(OutputType::NoSound)?;
+    studio.initialize(32, FMOD_STUDIO_INIT_NORMAL, FMOD_INIT_NORMAL, None)?;
+    Ok(())
+}
+
+fn read_loaded(studio: &Studio, paths: &[impl AsRef<Path>]) -> Result<Project> {
+    let mut banks = Vec::new();
+    for path in paths {
+        let path = path.as_ref().to_string_lossy();
+        banks.push(studio.load_bank_file(&path, FMOD_STUDIO_LOAD_BANK_NORMAL)?);
+    }
+
+    let mut project = Project::default();
+
+    for bank in &banks {
+        for index in 0..bank.get_string_count()? {
+            // Without a buffer, FMOD only reports the size the path needs.
+            let (_, _, size) = bank.get_string_info(index, 0)?;
+            let (guid, path, _) = bank.get_string_info(index, size)?;
+            project.insert(&path, guid.into());
+        }
+    }
+
+    for event in project
+        .events
+        .iter_mut()
+        .chain(project.snapshots.iter_mut())
+    {
+        // Events of banks that were not passed have no descriptions.
+        if let Ok(description) = studio.get_event_by_id(event.guid.into()) {
+            event.parameters = local_parameters(description)?;
+        }
+    }
+
+    let count = studio.get_parameter_description_count()?;
+    for description in studio.get_parameter_description_list(count)? {
+        match project
+            .parameters
+            .iter_mut()
+            .find(|parameter| parameter.name == description.name)
+        {
+            Some(parameter) => parameter.range = Some(description.minimum..=description.maximum),
+            None => project.parameters.push(parameter(&description)),
+        }
+    }
+
+    Ok(project)
+}
+
+/// The parameters of an event that can be set from code.
+fn local_parameters(description: EventDescription) -> Result<Vec<Parameter>> {
+    let mut parameters = Vec::new();
+
+    for index in 0..description.get_parameter_description_count()? {
+        let parameter_description = description.get_parameter_description_by_index(index)?;
+
+        // Automatic parameters like distance are set by FMOD itself, and global parameters are
+        // listed separately.
+        if parameter_description.type_ == ParameterType::GameControlled
+            && parameter_description.flags & FMOD_STUDIO_PARAMETER_GLOBAL == 0
+        {
+            parameters.push(parameter(&parameter_description));
+        }
+    }
+
+    Ok(parameters)
+}
+
+fn parameter(description: &ParameterDescription) -> Parameter {
+    Parameter {
+        name: description.name.clone(),
+        range: Some(description.minimum..=description.maximum),
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::Result;
+use crate::error::Error;
+use crate::project::{Entry, Event, Guid, Parameter, Project};
+
+/// Words that can't be used as module names.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self",
+    "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+    "use", "virtual", "where", "while", "yield",
+];
+
+/// Writes the Rust module generated by [`render`] to `file_name` in `OUT_DIR` and returns its
+/// path. Include it in your crate with
+/// `include!(concat!(env!("OUT_DIR"), "/<file_name>"));`.
+pub fn generate(project: &Project, file_name: &str) -> Result<PathBuf> {
+    let out_dir = std::env::var_os("OUT_DIR").ok_or(Error::NoOutDir)?;
+    let path = PathBuf::from(out_dir).join(file_name);
+
+    std::fs::write(&path, render(project))?;
+    Ok(path)
+}
+
+/// Renders the project as Rust modules of typed constants from `bevy_fmod::studio_paths`:
+///
+/// - `events` and `snapshots` hold an `EventPath` per event, in modules following the folders of
+///   the project. Next to each event is a module of the same name with a `ParameterName` per
+///   local parameter.
+/// - `buses` and `vcas` hold a `BusPath` or `VcaPath` per bus or VCA.
+/// - `parameters` holds a `ParameterName` per global parameter.
+///
+/// Names are converted to `SCREAMING_SNAKE_CASE` for constants and `snake_case` for modules, so
+/// `event:/Music/Level 01` becomes `events::music::LEVEL_01`.
+pub fn render(project: &Project) -> String {
+    let mut root = Module::new("");
+
+    let events = root.module("events", "The events of the project.");
+    for event in &project.events {
+        add_event(events, event, "event:/");
+    }
+
+    let snapshots = root.module("snapshots", "The snapshots of the project.");
+    for snapshot in &project.snapshots {
+        add_event(snapshots, snapshot, "snapshot:/");
+    }
+
+    let buses = root.module("buses", "The buses of the project.");
+    for bus in &project.buses {
+        add_entry(buses, bus, "bus:/", "BusPath", "MASTER");
+    }
+
+    let vcas = root.module("vcas", "The VCAs of the project.");
+    for vca in &project.vcas {
+        add_entry(vcas, vca, "vca:/", "VcaPath", "VCA");
+    }
+
+    let parameters = root.module("parameters", "The global parameters of the project.");
+    for parameter in &project.parameters {
+        add_parameter(parameters, parameter);
+    }
+
+    let mut output = String::new();
+    for (name, module) in &root.modules {
+        module.write(&mut output, name, 0);
+    }
+    output
+}
+
+fn add_event(root: &mut Module, event: &Event, prefix: &str) {
+    let (folder, name) = split_path(&event.path, prefix);
+    let module = folder_module(root, &folder);
+
+    module.constant(
+        &identifier(name, true, "EVENT"),
+        &event.path,
+        format!(
+            "::bevy_fmod::studio_paths::EventPath::new({:?}, {})",
+            event.path,
+            guid(event.guid)
+        ),
+        "EventPath",
+    );
+
+    if !event.parameters.is_empty() {
+        let parameters = module.module(
+            &identifier(name, false, "event"),
+            &format!("The local parameters of `{}`.", event.path),
+        );
+
+        for parameter in &event.parameters {
+            add_parameter(parameters, parameter);
+        }
+    }
+}
+
+fn add_entry(
+    root: &mut Module,
+    entry: &Entry,
+    prefix: &str,
+    type_name: &'static str,
+    fallback: &str,
+) {
+    let (folder, name) = split_path(&entry.path, prefix);
+    let module = folder_module(root, &folder);
+
+    module.constant(
+        &identifier(name, true, fallback),
+        &entry.path,
+        format!(
+            "::bevy_fmod::studio_paths::{type_name}::new({:?}, {})",
+            entry.path,
+            guid(entry.guid)
+        ),
+        type_name,
+    );
+}
+
+fn add_parameter(module: &mut Module, parameter: &Parameter) {
+    let mut value = format!(
+        "::bevy_fmod::studio_paths::ParameterName::new({:?})",
+        parameter.name
+    );
+
+    if let Some(range) = &parameter.range {
+        let _ = write!(value, ".with_range({:?}, {:?})", range.start(), range.end());
+    }
+
+    module.constant(
+        &identifier(&parameter.name, true, "PARAMETER"),
+        &parameter.name,
+        value,
+        "ParameterName",
+    );
+}
+
+/// Splits `event:/Music/Level 01` into the folders `["Music"]` and the name `Level 01`.
+fn split_path<'a>(path: &'a str, prefix: &str) -> (Vec<&'a str>, &'a str) {
+    let path = path.strip_prefix(prefix).unwrap_or(path);
+    let mut segments: Vec<&str> = path.split('/').collect();
+    let name = segments.pop().unwrap_or_default();
+    (segments, name)
+}
+
+fn folder_module<'a>(root: &'a mut Module, folders: &[&str]) -> &'a mut Module {
+    folders.iter().fold(root, |module, folder| {
+        module.module(
+            &identifier(folder, false, "folder"),
+            &format!("The `{folder}` folder."),
+        )
+    })
+}
+
+fn guid(guid: Guid) -> String {
+    format!(
+        "::bevy_fmod::libfmod::Guid {{ data_1: {:#010x}, data_2: {:#06x}, data_3: {:#06x}, data_4: {:?} }}",
+        guid.data_1, guid.data_2, guid.data_3, guid.data_4
+    )
+}
+
+/// Converts a name like `Car Engine` or `CarEngine` to `CAR_ENGINE` or `car_engine`.
+fn identifier(name: &str, upper: bool, fallback: &str) -> String {
+    let mut identifier = String::new();
+    let mut previous: Option<char> = None;
+
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            let word_start = previous.is_some_and(|previous| {
+                !previous.is_ascii_alphanumeric()
+                    || (c.is_ascii_uppercase() && previous.is_ascii_lowercase())
+            });
+
+            if word_start && !identifier.is_empty() {
+                identifier.push('_');
+            }
+
+            identifier.push(if upper {
+                c.to_ascii_uppercase()
+            } else {
+                c.to_ascii_lowercase()
+            });
+        }
+
+        previous = Some(c);
+    }
+
+    if identifier.is_empty() {
+        return fallback.to_string();
+    }
+
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) || KEYWORDS.contains(&&*identifier) {
+        identifier.insert(0, '_');
+    }
+
+    identifier
+}
+
+struct Module {
+    doc: String,
+    modules: BTreeMap<String, Module>,
+    constants: BTreeMap<String, (String, String, &'static str)>,
+}
+
+impl Module {
+    fn new(doc: &str) -> Self {
+        Module {
+            doc: doc.to_string(),
+            modules: BTreeMap::new(),
+            constants: BTreeMap::new(),
+        }
+    }
+
+    /// The submodule with the given name, created if needed.
+    fn module(&mut self, name: &str, doc: &str) -> &mut Module {
+        self.modules
+            .entry(name.to_string())
+            .or_insert_with(|| Module::new(doc))
+    }
+
+    /// Adds a constant, renamed with a number if the name is taken.
+    fn constant(&mut self, name: &str, doc: &str, value: String, type_name: &'static str) {
+        let mut unique = name.to_string();
+        let mut number = 2;
+
+        while self.constants.contains_key(&unique) {
+            unique = format!("{name}_{number}");
+            number += 1;
+        }
+
+        self.constants
+            .insert(unique, (doc.to_string(), value, type_name));
+    }
+
+    fn write(&self, output: &mut String, name: &str, depth: usize) {
+        let indent = "    ".repeat(depth);
+
+        let _ = writeln!(output, "{indent}#[doc = {:?}]", self.doc);
+        if depth == 0 {
+            let _ = writeln!(output, "#[allow(dead_code)]");
+        }
+        let _ = writeln!(output, "{indent}pub mod {name} {{");
+
+        for (name, (doc, value, type_name)) in &self.constants {
+            let _ = writeln!(output, "{indent}    #[doc = {:?}]", format!("`{doc}`"));
+            let _ = writeln!(
+                output,
+                "{indent}    pub const {name}: ::bevy_fmod::studio_paths::{type_name} = {value};"
+            );
+        }
+
+        for (name, module) in &self.modules {
+            module.write(output, name, depth + 1);
+        }
+
+        let _ = writeln!(output, "{indent}}}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guid() -> Guid {
+        Guid {
+            data_1: 1,
+            data_2: 2,
+            data_3: 3,
+            data_4: [4; 8],
+        }
+    }
+
+    fn event(path: &str, parameters: &[&str]) -> Event {
+        Event {
+            path: path.to_string(),
+            guid: guid(),
+            parameters: parameters
+                .iter()
+                .map(|name| Parameter {
+                    name: name.to_string(),
+                    range: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn converts_names_to_identifiers() {
+        assert_eq!(identifier("Car Engine", true, "EVENT"), "CAR_ENGINE");
+        assert_eq!(identifier("CarEngine", false, "event"), "car_engine");
+        assert_eq!(
+            identifier("Level-01 (Intro)", true, "EVENT"),
+            "LEVEL_01_INTRO"
+        );
+        assert_eq!(identifier("RPM", true, "PARAMETER"), "RPM");
+        assert_eq!(identifier("01 Intro", true, "EVENT"), "_01_INTRO");
+        assert_eq!(identifier("Type", false, "folder"), "_type");
+        assert_eq!(identifier("Self", false, "folder"), "_self");
+        assert_eq!(identifier("Größe", true, "PARAMETER"), "GR_E");
+        assert_eq!(identifier("🔊", true, "EVENT"), "EVENT");
+        assert_eq!(identifier("", false, "folder"), "folder");
+    }
+
+    #[test]
+    fn numbers_clashing_constants() {
+        let project = Project {
+            events: vec![
+                event("event:/Car Engine", &[]),
+                event("event:/Car-Engine", &[]),
+            ],
+            ..Project::default()
+        };
+
+        let output = render(&project);
+
+        assert!(output.contains("pub const CAR_ENGINE: "), "{output}");
+        assert!(output.contains("pub const CAR_ENGINE_2: "), "{output}");
+    }
+
+    #[test]
+    fn merges_folders_and_parameters_of_the_same_name() {
+        // The parameters of `Vehicles/Car` and the events in the `Vehicles/Car` folder end up in
+        // the same `car` module, so clashing names are numbered.
+        let project = Project {
+            events: vec![
+                event("event:/Vehicles/Car", &["RPM", "Horn"]),
+                event("event:/Vehicles/Car/Horn", &[]),
+            ],
+            ..Project::default()
+        };
+
+        let output = render(&project);
+
+        assert_eq!(output.matches("pub mod car {").count(), 1, "{output}");
+        assert!(output.contains("pub const RPM: "), "{output}");
+        assert!(output.contains("pub const HORN: "), "{output}");
+        assert!(output.contains("pub const HORN_2: "), "{output}");
+        assert!(output.contains("\"event:/Vehicles/Car/Horn\""), "{output}");
+    }
+
+    #[test]
+    fn renders_typed_constants() {
+        let project = Project {
+            events: vec![event("event:/Music/Level 01", &[])],
+            buses: vec![Entry {
+                path: "bus:/".to_string(),
+                guid: guid(),
+            }],
+            parameters: vec![Parameter {
+                name: "Weather".to_string(),
+                range: Some(0.0..=1.0),
+            }],
+            ..Project::default()
+        };
+
+        let output = render(&project);
+
+        assert!(output.contains("pub mod events {"), "{output}");
+        assert!(output.contains("pub mod music {"), "{output}");
+        assert!(
+            output.contains(
+                "pub const LEVEL_01: ::bevy_fmod::studio_paths::EventPath = \
+             ::bevy_fmod::studio_paths::EventPath::new(\"event:/Music/Level 01\", \
+             ::bevy_fmod::libfmod::Guid { data_1: 0x00000001, data_2: 0x0002, data_3: 0x0003, \
+             data_4: [4, 4, 4, 4, 4, 4, 4, 4] });"
+            ),
+            "{output}"
+        );
+        assert!(
+            output.contains("pub const MASTER: ::bevy_fmod::studio_paths::BusPath"),
+            "{output}"
+        );
+        assert!(
+            output.contains("ParameterName::new(\"Weather\").with_range(0.0, 1.0);"),
+            "{output}"
+        );
+    }
+}
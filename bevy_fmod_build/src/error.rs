@@ -0,0 +1,29 @@
+//! Error types for bevy_fmod_build
+
+use thiserror::Error;
+
+/// Error types
+#[derive(Error, Debug)]
+pub enum Error {
+    /// Errors coming from libfmod
+    #[cfg(feature = "banks")]
+    #[error(transparent)]
+    LibfmodError(#[from] libfmod::Error),
+    /// IO errors
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A line of a GUIDs.txt file could not be parsed
+    #[error("Invalid line {line} in GUIDs.txt: {content}")]
+    InvalidGuidsLine {
+        /// The line number, starting at 1
+        line: usize,
+        /// The content of the line
+        content: String,
+    },
+    /// Banks can only be read with the `banks` feature
+    #[error("Reading banks requires the `banks` feature of bevy_fmod_build")]
+    BanksFeatureDisabled,
+    /// The `OUT_DIR` environment variable is not set, e.g. outside of a build script
+    #[error("OUT_DIR is not set, generate() has to be called from a build script")]
+    NoOutDir,
+}
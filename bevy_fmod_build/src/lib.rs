@@ -0,0 +1,48 @@
+//! Build script helpers for [bevy_fmod](https://docs.rs/bevy_fmod).
+//!
+//! Generates a Rust module of typed constants for the events, snapshots, buses, VCAs and
+//! parameters of an FMOD Studio project, so that paths are checked by the compiler instead of
+//! failing at runtime. The constants can be passed to `FmodEvent::new`, `OneShot::new`,
+//! `FmodParameters` and the lookup functions of `FmodStudio`.
+//!
+//! Add this crate as a build dependency and generate the module in your `build.rs`:
+//!
+//! ```no_run
+//! // In `fn main` of build.rs:
+//! println!("cargo:rerun-if-changed=fmod/project/GUIDs.txt");
+//!
+//! let project = bevy_fmod_build::Project::from_guids("fmod/project/GUIDs.txt")
+//!     .expect("Failed to read GUIDs.txt");
+//! bevy_fmod_build::generate(&project, "fmod.rs").expect("Failed to generate FMOD paths");
+//! ```
+//!
+//! Then include it in your crate:
+//!
+//! ```ignore
+//! mod fmod {
+//!     include!(concat!(env!("OUT_DIR"), "/fmod.rs"));
+//! }
+//!
+//! commands.spawn(FmodEvent::new(fmod::events::music::LEVEL_01));
+//! ```
+//!
+//! `GUIDs.txt` is exported by FMOD Studio with `File > Export GUIDs...` and lists every path, but
+//! no parameters besides the names of global parameters. With the `banks` feature,
+//! [`Project::from_banks`] reads the built banks through FMOD instead, which also provides the
+//! local parameters of events and the ranges of all parameters. The FMOD libraries then have to
+//! be linkable and loadable by the build script, e.g. by adding their directories to the
+//! `rustflags` in `.cargo/config.toml` and to `LD_LIBRARY_PATH` on Linux.
+
+#![deny(missing_docs)]
+#![deny(clippy::unwrap_used, clippy::wildcard_imports)]
+
+#[cfg(feature = "banks")]
+mod banks;
+mod codegen;
+pub mod error;
+mod project;
+
+pub use codegen::{generate, render};
+pub use project::{Entry, Event, Guid, Parameter, Project};
+
+pub(crate) type Result<T> = std::result::Result<T, error::Error>;
@@ -0,0 +1,324 @@
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use crate::Result;
+use crate::error::Error;
+
+/// The contents of an FMOD Studio project: its events, snapshots, buses, VCAs and global
+/// parameters.
+#[derive(Debug, Clone, Default)]
+pub struct Project {
+    /// The events, sorted by path.
+    pub events: Vec<Event>,
+    /// The snapshots, sorted by path.
+    pub snapshots: Vec<Event>,
+    /// The buses, sorted by path.
+    pub buses: Vec<Entry>,
+    /// The VCAs, sorted by path.
+    pub vcas: Vec<Entry>,
+    /// The global parameters, sorted by name.
+    pub parameters: Vec<Parameter>,
+}
+
+/// An event or snapshot.
+#[derive(Debug, Clone)]
+pub struct Event {
+    /// The full path, e.g. `event:/Music/Level 01`.
+    pub path: String,
+    /// The GUID.
+    pub guid: Guid,
+    /// The local parameters. Only known when the project was read from banks.
+    pub parameters: Vec<Parameter>,
+}
+
+/// A bus or VCA.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// The full path, e.g. `bus:/SFX`.
+    pub path: String,
+    /// The GUID.
+    pub guid: Guid,
+}
+
+/// A local or global parameter.
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    /// The name of the parameter.
+    pub name: String,
+    /// The range of values. Only known when the project was read from banks.
+    pub range: Option<RangeInclusive<f32>>,
+}
+
+/// The GUID of an FMOD Studio object, laid out like `FMOD_GUID`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Guid {
+    /// The first 4 bytes.
+    pub data_1: u32,
+    /// The next 2 bytes.
+    pub data_2: u16,
+    /// The next 2 bytes.
+    pub data_3: u16,
+    /// The last 8 bytes.
+    pub data_4: [u8; 8],
+}
+
+impl Guid {
+    /// Parses a GUID in the format used by FMOD Studio, e.g.
+    /// `{b0bb0d5c-ac3b-4d7c-9a5e-2f1b6c4a7d3e}`.
+    pub fn parse(guid: &str) -> Option<Self> {
+        let guid = guid.strip_prefix('{')?.strip_suffix('}')?;
+        let groups: Vec<&str> = guid.split('-').collect();
+        let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+        let hex = groups.concat();
+
+        if lengths != [8, 4, 4, 4, 12] || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let byte = |index: usize| u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok();
+        let mut data_4 = [0; 8];
+        for (index, value) in data_4.iter_mut().enumerate() {
+            *value = byte(8 + index)?;
+        }
+
+        Some(Guid {
+            data_1: u32::from_str_radix(&hex[0..8], 16).ok()?,
+            data_2: u16::from_str_radix(&hex[8..12], 16).ok()?,
+            data_3: u16::from_str_radix(&hex[12..16], 16).ok()?,
+            data_4,
+        })
+    }
+}
+
+#[cfg(feature = "banks")]
+impl From<libfmod::Guid> for Guid {
+    fn from(guid: libfmod::Guid) -> Self {
+        Guid {
+            data_1: guid.data_1,
+            data_2: guid.data_2,
+            data_3: guid.data_3,
+            data_4: guid.data_4,
+        }
+    }
+}
+
+#[cfg(feature = "banks")]
+impl From<Guid> for libfmod::Guid {
+    fn from(guid: Guid) -> Self {
+        libfmod::Guid {
+            data_1: guid.data_1,
+            data_2: guid.data_2,
+            data_3: guid.data_3,
+            data_4: guid.data_4,
+        }
+    }
+}
+
+impl Project {
+    /// Reads the `GUIDs.txt` that FMOD Studio exports next to the project file with
+    /// `File > Export GUIDs...`.
+    ///
+    /// The file has no information about parameters besides the names of global parameters.
+    pub fn from_guids(path: impl AsRef<Path>) -> Result<Self> {
+        let mut project = Project::default();
+
+        for (index, line) in std::fs::read_to_string(path)?.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let invalid = || Error::InvalidGuidsLine {
+                line: index + 1,
+                content: line.to_string(),
+            };
+
+            let (guid, path) = line.split_once(' ').ok_or_else(invalid)?;
+            let guid = Guid::parse(guid).ok_or_else(invalid)?;
+
+            project.insert(path, guid);
+        }
+
+        project.sort();
+        Ok(project)
+    }
+
+    /// Reads banks through FMOD. The strings bank has to be one of them, as it holds the paths.
+    ///
+    /// Unlike [`from_guids`](Self::from_guids), this also reads the local parameters of events
+    /// and the ranges of all parameters from the other banks. Requires the `banks` feature.
+    pub fn from_banks(paths: &[impl AsRef<Path>]) -> Result<Self> {
+        #[cfg(feature = "banks")]
+        {
+            let mut project = crate::banks::read(paths)?;
+            project.sort();
+            Ok(project)
+        }
+
+        #[cfg(not(feature = "banks"))]
+        {
+            let _ = paths;
+            Err(Error::BanksFeatureDisabled)
+        }
+    }
+
+    /// Finds an event by its full path.
+    pub fn event(&self, path: &str) -> Option<&Event> {
+        self.events.iter().find(|event| event.path == path)
+    }
+
+    /// Finds a snapshot by its full path.
+    pub fn snapshot(&self, path: &str) -> Option<&Event> {
+        self.snapshots.iter().find(|snapshot| snapshot.path == path)
+    }
+
+    /// Adds the object with the given path, sorted into its kind by the path prefix. Banks and
+    /// unknown kinds are ignored.
+    pub(crate) fn insert(&mut self, path: &str, guid: Guid) {
+        let path = path.to_string();
+
+        if path.starts_with("event:/") {
+            self.events.push(Event {
+                path,
+                guid,
+                parameters: Vec::new(),
+            });
+        } else if path.starts_with("snapshot:/") {
+            self.snapshots.push(Event {
+                path,
+                guid,
+                parameters: Vec::new(),
+            });
+        } else if path.starts_with("bus:/") {
+            self.buses.push(Entry { path, guid });
+        } else if path.starts_with("vca:/") {
+            self.vcas.push(Entry { path, guid });
+        } else if let Some(name) = path.strip_prefix("parameter:/") {
+            self.parameters.push(Parameter {
+                name: name.to_string(),
+                range: None,
+            });
+        }
+    }
+
+    fn sort(&mut self) {
+        self.events.sort_by(|a, b| a.path.cmp(&b.path));
+        self.snapshots.sort_by(|a, b| a.path.cmp(&b.path));
+        self.buses.sort_by(|a, b| a.path.cmp(&b.path));
+        self.vcas.sort_by(|a, b| a.path.cmp(&b.path));
+        self.parameters.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUID: &str = "{b0bb0d5c-ac3b-4d7c-9a5e-2f1b6c4a7d3e}";
+
+    #[test]
+    fn parses_studio_guids() {
+        assert_eq!(
+            Guid::parse(GUID),
+            Some(Guid {
+                data_1: 0xb0bb0d5c,
+                data_2: 0xac3b,
+                data_3: 0x4d7c,
+                data_4: [0x9a, 0x5e, 0x2f, 0x1b, 0x6c, 0x4a, 0x7d, 0x3e],
+            })
+        );
+        assert_eq!(
+            Guid::parse(&GUID.to_uppercase()),
+            Guid::parse(GUID),
+            "hex digits are case-insensitive"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_guids() {
+        for guid in [
+            "",
+            "b0bb0d5c-ac3b-4d7c-9a5e-2f1b6c4a7d3e",
+            "{b0bb0d5c-ac3b-4d7c-9a5e-2f1b6c4a7d3}",
+            "{b0bb0d5cac3b4d7c9a5e2f1b6c4a7d3e00}",
+            "{b0bb0d5c-ac3b-4d7c-9a5e-2f1b6c4a7d3g}",
+            "{+0bb0d5c-ac3b-4d7c-9a5e-2f1b6c4a7d3e}",
+            "{b0bb0d5cac3b-4d7c-9a5e-2f1b6c4a7d3e-}",
+            "{b0bb0d5c-ac3b4-d7c-9a5e-2f1b6c4a7d3e}",
+        ] {
+            assert_eq!(Guid::parse(guid), None, "{guid}");
+        }
+    }
+
+    /// Writes `contents` to a file that is removed when the returned guard is dropped.
+    struct TempFile(std::path::PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, contents: &str) -> Result<Self> {
+            let path = std::env::temp_dir()
+                .join(format!("bevy_fmod_build-{}-{name}.txt", std::process::id()));
+            std::fs::write(&path, contents)?;
+            Ok(TempFile(path))
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn reads_guids_file() -> Result<()> {
+        let file = TempFile::new(
+            "valid",
+            &format!(
+                "{GUID} event:/Music/Level 02\n\
+                 {GUID} event:/Music/Level 01\n\
+                 \n\
+                 {GUID} snapshot:/Underwater\n\
+                 {GUID} bus:/SFX\n\
+                 {GUID} vca:/Music\n\
+                 {GUID} parameter:/Weather\n\
+                 {GUID} bank:/Master\n"
+            ),
+        )?;
+
+        let project = Project::from_guids(&file.0)?;
+
+        let paths = |events: &[Event]| -> Vec<String> {
+            events.iter().map(|event| event.path.clone()).collect()
+        };
+        assert_eq!(
+            paths(&project.events),
+            ["event:/Music/Level 01", "event:/Music/Level 02"]
+        );
+        assert_eq!(paths(&project.snapshots), ["snapshot:/Underwater"]);
+        assert_eq!(project.buses[0].path, "bus:/SFX");
+        assert_eq!(project.vcas[0].path, "vca:/Music");
+        assert_eq!(project.parameters[0].name, "Weather");
+        assert_eq!(project.parameters[0].range, None);
+        assert!(
+            project
+                .event("event:/Music/Level 01")
+                .is_some_and(|event| Some(event.guid) == Guid::parse(GUID))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn reports_invalid_guids_lines() -> Result<()> {
+        let file = TempFile::new("invalid", &format!("{GUID} event:/Music\n\n{GUID}\n"))?;
+
+        match Project::from_guids(&file.0) {
+            Err(Error::InvalidGuidsLine { line, content }) => {
+                assert_eq!(line, 3);
+                assert_eq!(content, GUID);
+            }
+            result => panic!("expected an invalid line, got {result:?}"),
+        }
+
+        Ok(())
+    }
+}
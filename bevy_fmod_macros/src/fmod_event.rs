@@ -0,0 +1,226 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+use bevy_fmod_build::Project;
+use bevy_fmod_build::error::Error;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+use toml_edit::Document;
+
+/// The environment variable with the path of the project, relative to the crate.
+const PROJECT_VAR: &str = "BEVY_FMOD_PROJECT";
+
+/// Replaces the error of bevy_fmod_build, as its features are set through bevy_fmod.
+const BANKS_FEATURE_DISABLED: &str = "Reading a strings bank requires the `strings-bank` feature \
+                                      of bevy_fmod, use GUIDs.txt instead";
+
+/// The projects read so far, as the macro is expanded many times per build.
+static PROJECTS: Mutex<Vec<(PathBuf, SystemTime, Arc<Project>)>> = Mutex::new(Vec::new());
+
+pub(crate) fn expand(path: &LitStr) -> syn::Result<TokenStream> {
+    let error = |message: String| syn::Error::new(path.span(), message);
+
+    let source = project_source().map_err(error)?;
+    let project = read_project(&source).map_err(error)?;
+    let value = path.value();
+
+    let Some(event) = project.event(&value).or_else(|| project.snapshot(&value)) else {
+        let mut message = format!("{value} does not exist in {}", source.display());
+        if let Some(suggestion) = suggestion(&project, &value) {
+            message.push_str(&format!(", did you mean `{suggestion}`?"));
+        }
+        return Err(error(message));
+    };
+
+    let data_1 = event.guid.data_1;
+    let data_2 = event.guid.data_2;
+    let data_3 = event.guid.data_3;
+    let data_4 = event.guid.data_4;
+    let source = source.to_string_lossy();
+
+    Ok(quote! {{
+        // Makes Cargo rebuild the crate when the project changes.
+        const _: &[u8] = include_bytes!(#source);
+
+        ::bevy_fmod::studio_paths::EventPath::new(
+            #value,
+            ::bevy_fmod::libfmod::Guid {
+                data_1: #data_1,
+                data_2: #data_2,
+                data_3: #data_3,
+                data_4: [#(#data_4),*],
+            },
+        )
+    }})
+}
+
+/// The path of the GUIDs.txt or strings bank, from the environment variable or the
+/// `package.metadata.bevy_fmod.project` key of the crate's manifest.
+fn project_source() -> Result<PathBuf, String> {
+    let manifest_dir = PathBuf::from(
+        std::env::var_os("CARGO_MANIFEST_DIR").ok_or("CARGO_MANIFEST_DIR is not set")?,
+    );
+
+    if let Some(path) = std::env::var_os(PROJECT_VAR) {
+        return Ok(manifest_dir.join(path));
+    }
+
+    let manifest_path = manifest_dir.join("Cargo.toml");
+    let manifest = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read {}: {e}", manifest_path.display()))?;
+    let manifest = Document::parse(manifest)
+        .map_err(|e| format!("Failed to parse {}: {e}", manifest_path.display()))?;
+
+    manifest
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("bevy_fmod"))
+        .and_then(|bevy_fmod| bevy_fmod.get("project"))
+        .and_then(|project| project.as_str())
+        .map(|project| manifest_dir.join(project))
+        .ok_or_else(|| {
+            format!(
+                "Set {PROJECT_VAR} or `package.metadata.bevy_fmod.project` in Cargo.toml to the \
+                 GUIDs.txt or strings bank of the FMOD Studio project"
+            )
+        })
+}
+
+fn read_project(source: &Path) -> Result<Arc<Project>, String> {
+    let modified = std::fs::metadata(source)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| format!("Failed to read {}: {e}", source.display()))?;
+
+    let mut projects = PROJECTS.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let Some((_, _, project)) = projects
+        .iter()
+        .find(|(path, time, _)| path == source && *time == modified)
+    {
+        return Ok(project.clone());
+    }
+
+    let project = if source
+        .extension()
+        .is_some_and(|extension| extension == "bank")
+    {
+        Project::from_banks(&[source])
+    } else {
+        Project::from_guids(source)
+    };
+
+    let project = Arc::new(project.map_err(|e| match e {
+        Error::BanksFeatureDisabled => BANKS_FEATURE_DISABLED.to_string(),
+        e => format!("Failed to read {}: {e}", source.display()),
+    })?);
+
+    projects.retain(|(path, _, _)| path != source);
+    projects.push((source.to_path_buf(), modified, project.clone()));
+
+    Ok(project)
+}
+
+/// The closest event or snapshot path, if it is close enough to be a typo.
+fn suggestion<'a>(project: &'a Project, path: &str) -> Option<&'a str> {
+    let max_distance = (path.len() / 4).max(2);
+
+    project
+        .events
+        .iter()
+        .chain(&project.snapshots)
+        .map(|event| (distance(path, &event.path), event.path.as_str()))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, path)| path)
+}
+
+/// The Levenshtein distance between two strings, ignoring case.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a) in a.iter().enumerate() {
+        current[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_fmod_build::{Event, Guid};
+
+    use super::*;
+
+    fn project(events: &[&str], snapshots: &[&str]) -> Project {
+        let event = |path: &&str| Event {
+            path: path.to_string(),
+            guid: Guid {
+                data_1: 0,
+                data_2: 0,
+                data_3: 0,
+                data_4: [0; 8],
+            },
+            parameters: Vec::new(),
+        };
+
+        Project {
+            events: events.iter().map(event).collect(),
+            snapshots: snapshots.iter().map(event).collect(),
+            ..Project::default()
+        }
+    }
+
+    #[test]
+    fn distance_counts_edits() {
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("forest", ""), 6);
+        assert_eq!(distance("", "forest"), 6);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("forest", "forrest"), 1);
+        assert_eq!(distance("forest", "froest"), 2);
+        assert_eq!(distance("Forest", "forest"), 0, "case is ignored");
+        assert_eq!(distance("äpfel", "apfel"), 1, "characters, not bytes");
+    }
+
+    #[test]
+    fn suggests_the_closest_path() {
+        let project = project(
+            &["event:/Ambience/Forest", "event:/Ambience/Forest Night"],
+            &["snapshot:/Underwater"],
+        );
+
+        assert_eq!(
+            suggestion(&project, "event:/Ambience/Forrest"),
+            Some("event:/Ambience/Forest")
+        );
+        assert_eq!(
+            suggestion(&project, "event:/ambience/forest night"),
+            Some("event:/Ambience/Forest Night")
+        );
+        assert_eq!(
+            suggestion(&project, "snapshot:/Underwatr"),
+            Some("snapshot:/Underwater")
+        );
+    }
+
+    #[test]
+    fn does_not_suggest_unrelated_paths() {
+        let project = project(&["event:/Ambience/Forest"], &[]);
+
+        assert_eq!(suggestion(&project, "event:/Music/Level 01"), None);
+        assert_eq!(suggestion(&Project::default(), "event:/Forest"), None);
+    }
+}
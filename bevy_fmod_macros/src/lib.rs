@@ -0,0 +1,232 @@
+//! Macros for [bevy_fmod](https://docs.rs/bevy_fmod). Use them through the re-exports in
+//! `bevy_fmod` instead of depending on this crate directly.
+
+mod fmod_event;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, parse_macro_input};
+
+/// Implements `bevy_fmod::event_parameters::EventParameters` for a struct, mapping each field to
+/// the local parameter of the same name.
+///
+/// Attributes:
+/// - `#[fmod(name = "...")]` on a field uses a different parameter name.
+/// - `#[fmod(skip)]` on a field excludes it.
+/// - `#[fmod(ignore_seek_speed)]` on the struct makes parameters jump to their new values.
+#[proc_macro_derive(FmodParameters, attributes(fmod))]
+pub fn derive_fmod_parameters(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Checks the path of an event or snapshot against the FMOD Studio project at compile time and
+/// expands to a `bevy_fmod::studio_paths::EventPath`.
+///
+/// The project is read from the `GUIDs.txt` exported by FMOD Studio or, with the `strings-bank`
+/// feature of bevy_fmod, from the strings bank. Its path, relative to the crate, is set with the
+/// `BEVY_FMOD_PROJECT` environment variable or in the crate's `Cargo.toml`:
+///
+/// ```toml
+/// [package.metadata.bevy_fmod]
+/// project = "fmod/project/GUIDs.txt"
+/// ```
+///
+/// Paths that don't exist fail to compile, with a suggestion if there is a similar path.
+#[proc_macro]
+pub fn fmod_event(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+
+    match fmod_event::expand(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "FmodParameters can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &data.fields,
+            "FmodParameters can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut ignore_seek_speed = false;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("fmod"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("ignore_seek_speed") {
+                ignore_seek_speed = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `ignore_seek_speed`"))
+            }
+        })?;
+    }
+
+    let mut names = Vec::new();
+    let mut idents = Vec::new();
+
+    for field in &fields.named {
+        let Some(ident) = &field.ident else {
+            continue;
+        };
+
+        let mut name = ident.to_string();
+        let mut skip = false;
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("fmod"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name = \"...\"` or `skip`"))
+                }
+            })?;
+        }
+
+        if !skip {
+            names.push(name);
+            idents.push(ident);
+        }
+    }
+
+    let indices = 0..idents.len();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::bevy_fmod::event_parameters::EventParameters
+            for #ident #ty_generics #where_clause
+        {
+            const PARAMETER_NAMES: &'static [&'static str] = &[#(#names),*];
+            const IGNORE_SEEK_SPEED: bool = #ignore_seek_speed;
+
+            fn parameter_value(&self, index: usize) -> f32 {
+                match index {
+                    #(#indices => ::bevy_fmod::event_parameters::ParameterValue::parameter_value(
+                        &self.#idents
+                    ),)*
+                    _ => 0.0,
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{DeriveInput, parse_quote};
+
+    use super::expand;
+
+    #[test]
+    fn maps_fields_to_parameters() -> syn::Result<()> {
+        let input: DeriveInput = parse_quote! {
+            #[fmod(ignore_seek_speed)]
+            struct Engine {
+                #[fmod(name = "RPM")]
+                rpm: f32,
+                #[fmod(skip)]
+                smoothed: f32,
+                load: f32,
+            }
+        };
+
+        let expected = quote::quote! {
+            impl ::bevy_fmod::event_parameters::EventParameters for Engine {
+                const PARAMETER_NAMES: &'static [&'static str] = &["RPM", "load"];
+                const IGNORE_SEEK_SPEED: bool = true;
+
+                fn parameter_value(&self, index: usize) -> f32 {
+                    match index {
+                        0usize => ::bevy_fmod::event_parameters::ParameterValue::parameter_value(
+                            &self.rpm
+                        ),
+                        1usize => ::bevy_fmod::event_parameters::ParameterValue::parameter_value(
+                            &self.load
+                        ),
+                        _ => 0.0,
+                    }
+                }
+            }
+        };
+
+        assert_eq!(expand(&input)?.to_string(), expected.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_generics() -> syn::Result<()> {
+        let input: DeriveInput = parse_quote! {
+            struct Wrapper<T: Send> where T: Sync {
+                value: f32,
+                marker: T,
+            }
+        };
+
+        let expanded = expand(&input)?.to_string();
+
+        assert!(expanded.contains("impl < T : Send >"), "{expanded}");
+        assert!(
+            expanded.contains("for Wrapper < T > where T : Sync"),
+            "{expanded}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_enums_and_tuple_structs() {
+        let input: DeriveInput = parse_quote! {
+            enum Surface { Grass, Stone }
+        };
+        assert!(expand(&input).is_err());
+
+        let input: DeriveInput = parse_quote! {
+            struct Height(f32);
+        };
+        assert!(expand(&input).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_attributes() {
+        let input: DeriveInput = parse_quote! {
+            #[fmod(seek_speed)]
+            struct Engine {
+                rpm: f32,
+            }
+        };
+        assert!(expand(&input).is_err());
+
+        let input: DeriveInput = parse_quote! {
+            struct Engine {
+                #[fmod(rename = "RPM")]
+                rpm: f32,
+            }
+        };
+        assert!(expand(&input).is_err());
+    }
+}
@@ -20,17 +20,15 @@
 //!     App::new()
 //!         .add_plugins((
 //!             DefaultPlugins,
-//!             FmodPlugin {
-//!                 audio_banks_paths: &["<path to your audio banks>"],
-//!             },
+//!             FmodPlugin::new(&["<path to your audio banks>"]),
 //!         ))
 //!         // ...
 //!         .add_systems(Update, (set_some_global_parameter))
 //!         .run();
 //! }
 //!
-//! fn set_some_global_parameter(studio: Res<FmodStudio>) {
-//!    studio.0.set_parameter_by_name("SomeGlobalParameter", 0.5, false).unwrap();
+//! fn set_some_global_parameter(mut parameters: ResMut<GlobalParameters>) {
+//!     parameters.set("SomeGlobalParameter", 0.5);
 //! }
 //! ```
 
@@ -63,10 +61,14 @@ struct CountrySfxPlayer;
 fn startup(mut commands: Commands, studio: Res<FmodStudio>) {
     let event_description = studio.get_event("event:/Ambience/Forest").unwrap();
 
-    commands.spawn(ForestSfxPlayer).insert(AudioSource {
-        event_instance: event_description.create_instance().unwrap(),
-        despawn_stop_mode: StopMode::AllowFadeout,
-    });
+    commands.spawn((
+        ForestSfxPlayer,
+        AudioSource {
+            event_instance: event_description.create_instance().unwrap(),
+            despawn_stop_mode: StopMode::AllowFadeout,
+        },
+        FmodParameters::new().with_parameter("Rain", 0.0),
+    ));
 
     let event_description = studio.get_event("event:/Ambience/Country").unwrap();
 
@@ -86,22 +88,18 @@ fn play_music(audio_sources: Query<&AudioSource>) {
 }
 
 fn set_rain(
-    audio_sources: Query<&AudioSource, With<ForestSfxPlayer>>,
+    mut parameters: Query<&mut FmodParameters, With<ForestSfxPlayer>>,
     input: Res<ButtonInput<KeyCode>>,
 ) {
     if input.just_pressed(KeyCode::ArrowUp) {
-        for audio_source in audio_sources.iter() {
-            audio_source
-                .set_parameter_by_name("Rain", 1.0, false)
-                .expect("Could not set parameter.");
+        for mut parameters in parameters.iter_mut() {
+            parameters.set("Rain", 1.0);
         }
     }
 
     if input.just_pressed(KeyCode::ArrowDown) {
-        for audio_source in audio_sources.iter() {
-            audio_source
-                .set_parameter_by_name("Rain", 0.0, false)
-                .expect("Could not set parameter.");
+        for mut parameters in parameters.iter_mut() {
+            parameters.set("Rain", 0.0);
         }
     }
 }
@@ -0,0 +1,254 @@
+//! Tracking the loading progress of the banks passed to the [`FmodPlugin`](crate::FmodPlugin).
+//!
+//! Banks are loaded with [`BankLoading::Blocking`](crate::BankLoading::Blocking) by default. With
+//! [`BankLoading::NonBlocking`](crate::BankLoading::NonBlocking), the app starts right away and
+//! the banks finish loading in the background. Both ways report through the same resource and
+//! messages, which makes it easy to drive loading screens and state transitions:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_fmod::prelude::*;
+//! # #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+//! # enum GameState { #[default] Loading, Playing }
+//! fn finish_loading(mut next_state: ResMut<NextState<GameState>>) {
+//!     next_state.set(GameState::Playing);
+//! }
+//!
+//! App::new().add_systems(
+//!     Update,
+//!     finish_loading.run_if(in_state(GameState::Loading).and(banks_loaded)),
+//! );
+//! ```
+
+use bevy::log::{debug, error};
+use bevy::prelude::{App, Message, MessageWriter, Res, ResMut, Resource};
+use libfmod::{Bank, LoadingState};
+
+use crate::error::Error;
+use crate::fmod_settings::FmodSettings;
+use crate::fmod_studio::FmodStudio;
+
+/// Sent once a bank and, if requested, its sample data finished loading.
+#[derive(Message, Debug, Clone)]
+pub struct BankLoaded {
+    /// The path the bank was loaded from.
+    pub path: String,
+    /// The loaded bank.
+    pub bank: Bank,
+}
+
+/// Sent when a bank or its sample data failed to load.
+#[derive(Message, Debug, Clone)]
+pub struct BankLoadFailed {
+    /// The path the bank was loaded from.
+    pub path: String,
+    /// Why the bank failed to load.
+    pub reason: String,
+}
+
+/// The loading state of a single bank in the [`BankLoadingProgress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BankLoadState {
+    /// The bank's metadata is loading.
+    Loading,
+    /// The bank's metadata is loaded and its sample data is loading.
+    LoadingSampleData,
+    /// The bank is completely loaded.
+    Loaded,
+    /// The bank failed to load.
+    Failed,
+}
+
+/// The loading progress of the banks passed to the [`FmodPlugin`](crate::FmodPlugin).
+///
+/// Updated every frame in `PreUpdate` until all banks are loaded.
+#[derive(Resource, Debug, Default)]
+pub struct BankLoadingProgress {
+    banks: Vec<TrackedBank>,
+}
+
+#[derive(Debug)]
+struct TrackedBank {
+    path: String,
+    bank: Option<Bank>,
+    load_sample_data: bool,
+    state: BankLoadState,
+}
+
+impl TrackedBank {
+    /// The number of loading steps of this bank: its metadata and optionally its sample data.
+    fn steps(&self) -> usize {
+        if self.load_sample_data { 2 } else { 1 }
+    }
+
+    fn finished_steps(&self) -> usize {
+        match self.state {
+            BankLoadState::Loading => 0,
+            BankLoadState::LoadingSampleData => 1,
+            BankLoadState::Loaded | BankLoadState::Failed => self.steps(),
+        }
+    }
+}
+
+impl BankLoadingProgress {
+    /// The loading progress of all banks from `0.0` to `1.0`, including their sample data if
+    /// requested. Failed banks count as finished.
+    pub fn progress(&self) -> f32 {
+        let steps: usize = self.banks.iter().map(TrackedBank::steps).sum();
+
+        if steps == 0 {
+            return 1.0;
+        }
+
+        let finished_steps: usize = self.banks.iter().map(TrackedBank::finished_steps).sum();
+
+        finished_steps as f32 / steps as f32
+    }
+
+    /// Returns `true` once every bank is either loaded or failed to load.
+    pub fn is_finished(&self) -> bool {
+        self.banks
+            .iter()
+            .all(|bank| matches!(bank.state, BankLoadState::Loaded | BankLoadState::Failed))
+    }
+
+    /// Returns `true` if any bank failed to load.
+    pub fn has_failed(&self) -> bool {
+        self.banks
+            .iter()
+            .any(|bank| bank.state == BankLoadState::Failed)
+    }
+
+    /// The loading state of every bank, by the path it was loaded from.
+    pub fn banks(&self) -> impl Iterator<Item = (&str, BankLoadState)> {
+        self.banks
+            .iter()
+            .map(|bank| (bank.path.as_str(), bank.state))
+    }
+}
+
+impl BankLoadingProgress {
+    /// The banks which finished loading, by the path they were loaded from.
+    #[cfg(feature = "utilities")]
+    pub(crate) fn loaded_banks(&self) -> impl Iterator<Item = (&str, Bank)> {
+        self.banks.iter().filter_map(|tracked| match tracked.state {
+            BankLoadState::Loaded => Some((tracked.path.as_str(), tracked.bank?)),
+            _ => None,
+        })
+    }
+
+    /// Replaces the bank loaded from `path`, e.g. after it was reloaded.
+    #[cfg(feature = "utilities")]
+    pub(crate) fn replace_bank(&mut self, path: &str, bank: Bank) {
+        if let Some(tracked) = self.banks.iter_mut().find(|tracked| tracked.path == path) {
+            tracked.bank = Some(bank);
+        }
+    }
+}
+
+/// Run condition that returns `true` once every bank passed to the
+/// [`FmodPlugin`](crate::FmodPlugin) is either loaded or failed to load.
+pub fn banks_loaded(progress: Option<Res<BankLoadingProgress>>) -> bool {
+    progress.is_none_or(|progress| progress.is_finished())
+}
+
+/// Starts loading the given banks and inserts the [`BankLoadingProgress`].
+pub(crate) fn load_banks(
+    app: &mut App,
+    studio: &FmodStudio,
+    banks_paths: &[&'static str],
+    settings: &FmodSettings,
+) {
+    let flags = settings.bank_loading.flags();
+
+    let banks = banks_paths
+        .iter()
+        .map(|path| {
+            let (bank, state) = match studio.load_bank(path, flags) {
+                Ok(bank) => (Some(bank), BankLoadState::Loading),
+                Err(e) => {
+                    error!("Could not load bank {path}: {e}");
+                    app.world_mut().write_message(BankLoadFailed {
+                        path: path.to_string(),
+                        reason: e.to_string(),
+                    });
+                    (None, BankLoadState::Failed)
+                }
+            };
+
+            TrackedBank {
+                path: path.to_string(),
+                bank,
+                load_sample_data: settings.load_sample_data,
+                state,
+            }
+        })
+        .collect();
+
+    app.insert_resource(BankLoadingProgress { banks });
+}
+
+/// Polls the loading state of all banks that are still loading.
+pub(crate) fn update_bank_loading(
+    mut progress: ResMut<BankLoadingProgress>,
+    mut loaded: MessageWriter<BankLoaded>,
+    mut failed: MessageWriter<BankLoadFailed>,
+) {
+    if progress.is_finished() {
+        return;
+    }
+
+    for tracked in progress.banks.iter_mut() {
+        let Some(bank) = tracked.bank else {
+            continue;
+        };
+
+        let state = match tracked.state {
+            BankLoadState::Loading => poll_metadata(bank, tracked.load_sample_data),
+            BankLoadState::LoadingSampleData => poll_sample_data(bank),
+            BankLoadState::Loaded | BankLoadState::Failed => continue,
+        };
+
+        let state = match state {
+            Ok(state) => state,
+            Err(e) => {
+                error!("Could not load bank {}: {e}", tracked.path);
+                failed.write(BankLoadFailed {
+                    path: tracked.path.clone(),
+                    reason: e.to_string(),
+                });
+                BankLoadState::Failed
+            }
+        };
+
+        if state == BankLoadState::Loaded {
+            debug!("Loaded bank {}", tracked.path);
+            loaded.write(BankLoaded {
+                path: tracked.path.clone(),
+                bank,
+            });
+        }
+
+        tracked.state = state;
+    }
+}
+
+fn poll_metadata(bank: Bank, load_sample_data: bool) -> crate::Result<BankLoadState> {
+    // If loading failed, FMOD returns the reason as the error of this call.
+    match bank.get_loading_state()? {
+        LoadingState::Loaded if load_sample_data => {
+            bank.load_sample_data()?;
+            poll_sample_data(bank)
+        }
+        LoadingState::Loaded => Ok(BankLoadState::Loaded),
+        _ => Ok(BankLoadState::Loading),
+    }
+}
+
+fn poll_sample_data(bank: Bank) -> crate::Result<BankLoadState> {
+    match bank.get_sample_loading_state()? {
+        LoadingState::Loaded => Ok(BankLoadState::Loaded),
+        LoadingState::Error => Err(Error::SampleDataLoadFailed),
+        _ => Ok(BankLoadState::LoadingSampleData),
+    }
+}
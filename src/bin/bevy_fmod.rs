@@ -0,0 +1,622 @@
+//! Inspects the contents of FMOD banks without starting a game.
+//!
+//! ```text
+//! bevy_fmod inspect [--json] <bank or directory>...
+//! bevy_fmod diff [--json] <old bank or directory> <new bank or directory>
+//! ```
+//!
+//! Directories are searched for `.bank` files. Include the strings bank, otherwise events are
+//! listed by their GUID. `diff` exits with status 1 if the builds differ, so it can be used in CI.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::process::ExitCode;
+
+use bevy::MinimalPlugins;
+use bevy::prelude::{App, Messages};
+use bevy_fmod::Output;
+use bevy_fmod::event_catalog::{CatalogEvent, FmodEventCatalog, UserPropertyValue, label_count};
+use bevy_fmod::libfmod::ParameterDescription;
+use bevy_fmod::libfmod::ffi::{
+    FMOD_STUDIO_PARAMETER_AUTOMATIC, FMOD_STUDIO_PARAMETER_DISCRETE, FMOD_STUDIO_PARAMETER_GLOBAL,
+    FMOD_STUDIO_PARAMETER_LABELED, FMOD_STUDIO_PARAMETER_READONLY,
+};
+use bevy_fmod::prelude::{
+    BankLoadFailed, BankLoading, FmodPlugin, FmodSettings, FmodStatus, FmodStudio,
+};
+use serde_json::{Map, Value, json};
+
+const USAGE: &str = "Usage:
+  bevy_fmod inspect [--json] <bank or directory>...
+  bevy_fmod diff [--json] <old bank or directory> <new bank or directory>";
+
+/// The contents of a set of banks, read from the [`FmodEventCatalog`].
+struct Build {
+    banks: Vec<BankInfo>,
+    parameters: Vec<ParameterInfo>,
+}
+
+struct BankInfo {
+    path: String,
+    events: Vec<EventInfo>,
+    snapshots: Vec<EventInfo>,
+    buses: Vec<String>,
+    vcas: Vec<String>,
+}
+
+struct EventInfo {
+    path: String,
+    is_3d: bool,
+    is_oneshot: bool,
+    is_stream: bool,
+    length: i32,
+    min_distance: f32,
+    max_distance: f32,
+    user_properties: Vec<(String, Value)>,
+    parameters: Vec<ParameterInfo>,
+}
+
+struct ParameterInfo {
+    name: String,
+    minimum: f32,
+    maximum: f32,
+    default_value: f32,
+    flags: Vec<&'static str>,
+    labels: Vec<String>,
+}
+
+/// A line of the tree output: its depth, the name of the item and its details.
+type Line = (usize, String, String);
+
+/// The lines that differ between two builds, by their keys.
+struct Diff<'a> {
+    removed: Vec<&'a String>,
+    added: Vec<&'a String>,
+    changed: Vec<(&'a String, &'a String, &'a String)>,
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
+    let json = args.iter().any(|arg| arg == "--json");
+    args.retain(|arg| arg != "--json");
+
+    let result = match args.split_first() {
+        Some((command, paths)) if command == "inspect" && !paths.is_empty() => inspect(paths, json),
+        Some((command, [old, new])) if command == "diff" => diff(old, new, json),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn inspect(paths: &[String], json: bool) -> Result<ExitCode, String> {
+    let build = Build::read(paths)?;
+
+    if json {
+        println!("{}", build.json());
+    } else {
+        for (depth, name, details) in build.lines() {
+            println!("{}{name}{details}", "  ".repeat(depth));
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn diff(old: &str, new: &str, json: bool) -> Result<ExitCode, String> {
+    let old = Build::read(&[old.to_string()])?.keyed_lines();
+    let new = Build::read(&[new.to_string()])?.keyed_lines();
+    let diff = Diff::compare(&old, &new);
+
+    if json {
+        println!("{}", diff.json());
+    } else {
+        for key in &diff.removed {
+            println!("- {key}");
+        }
+        for key in &diff.added {
+            println!("+ {key}");
+        }
+        for (key, old, new) in &diff.changed {
+            println!("~ {key}\n    old:{old}\n    new:{new}");
+        }
+    }
+
+    if diff.is_empty() {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+impl<'a> Diff<'a> {
+    fn compare(old: &'a BTreeMap<String, String>, new: &'a BTreeMap<String, String>) -> Self {
+        Diff {
+            removed: old.keys().filter(|key| !new.contains_key(*key)).collect(),
+            added: new.keys().filter(|key| !old.contains_key(*key)).collect(),
+            changed: old
+                .iter()
+                .filter_map(|(key, old)| {
+                    new.get(key)
+                        .filter(|new| *new != old)
+                        .map(|new| (key, old, new))
+                })
+                .collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty() && self.changed.is_empty()
+    }
+
+    fn json(&self) -> Value {
+        json!({
+            "removed": self.removed,
+            "added": self.added,
+            "changed": self
+                .changed
+                .iter()
+                .map(|(key, old, new)| json!({
+                    "item": key,
+                    "old": old.trim(),
+                    "new": new.trim(),
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl Build {
+    /// Loads the banks in a headless app with the no-sound output and reads its catalog.
+    fn read(paths: &[String]) -> Result<Self, String> {
+        let mut bank_paths = Vec::new();
+        for path in paths {
+            collect_banks(Path::new(path), &mut bank_paths)?;
+        }
+
+        if bank_paths.is_empty() {
+            return Err(format!("No banks found in {}", paths.join(", ")));
+        }
+
+        // The plugin takes static paths, which is fine for a short-lived tool.
+        let bank_paths: Vec<&'static str> = bank_paths
+            .into_iter()
+            .map(|path| &*Box::leak(path.into_boxed_str()))
+            .collect();
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            FmodPlugin::new(Box::leak(bank_paths.into_boxed_slice())).with_settings(FmodSettings {
+                output: Output::NoSound,
+                bank_loading: BankLoading::Blocking,
+                ..FmodSettings::default()
+            }),
+        ));
+        app.finish();
+        app.cleanup();
+        app.update();
+
+        let world = app.world();
+
+        if let FmodStatus::Disabled { reason } = world.resource::<FmodStatus>() {
+            return Err(format!("Could not initialize FMOD: {reason}"));
+        }
+
+        // Failures of blocking loads are sent before the first update, so read all messages.
+        let failures = world.resource::<Messages<BankLoadFailed>>();
+        if let Some(failed) = failures.get_cursor().read(failures).next() {
+            return Err(format!(
+                "Could not load bank {}: {}",
+                failed.path, failed.reason
+            ));
+        }
+
+        let studio = world.resource::<FmodStudio>();
+        let catalog = world.resource::<FmodEventCatalog>();
+
+        let banks = catalog
+            .banks()
+            .iter()
+            .map(|bank| {
+                Ok(BankInfo {
+                    path: bank.path().to_string(),
+                    events: bank
+                        .events()
+                        .iter()
+                        .map(EventInfo::read)
+                        .collect::<Result<_, String>>()?,
+                    snapshots: bank
+                        .snapshots()
+                        .iter()
+                        .map(EventInfo::read)
+                        .collect::<Result<_, String>>()?,
+                    buses: bank.buses().to_vec(),
+                    vcas: bank.vcas().to_vec(),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        let parameters = catalog
+            .parameters()
+            .iter()
+            .map(|description| {
+                ParameterInfo::read(description, |label| {
+                    // Without a buffer, FMOD only reports the size the label needs.
+                    let (_, size) =
+                        studio.get_parameter_label_by_id(description.id.clone(), label, 0)?;
+                    studio
+                        .get_parameter_label_by_id(description.id.clone(), label, size)
+                        .map(|(label, _)| label)
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Build { banks, parameters })
+    }
+
+    fn lines(&self) -> Vec<Line> {
+        let mut lines = Vec::new();
+
+        for bank in &self.banks {
+            lines.push((0, bank.path.clone(), String::new()));
+
+            for event in bank.events.iter().chain(&bank.snapshots) {
+                event.lines(&mut lines);
+            }
+            for bus in bank.buses.iter().chain(&bank.vcas) {
+                lines.push((1, bus.clone(), String::new()));
+            }
+        }
+
+        lines.push((0, "Global parameters".to_string(), String::new()));
+        for parameter in &self.parameters {
+            lines.push(parameter.line(1));
+        }
+
+        lines
+    }
+
+    /// The lines by the names of all their ancestors, for comparing builds.
+    fn keyed_lines(&self) -> BTreeMap<String, String> {
+        let mut ancestors: Vec<String> = Vec::new();
+        let mut keyed_lines = BTreeMap::new();
+
+        for (depth, name, details) in self.lines() {
+            ancestors.truncate(depth);
+            ancestors.push(name);
+            keyed_lines.insert(ancestors.join(" > "), details);
+        }
+
+        keyed_lines
+    }
+
+    fn json(&self) -> Value {
+        json!({
+            "banks": self.banks.iter().map(BankInfo::json).collect::<Vec<_>>(),
+            "parameters": self.parameters.iter().map(ParameterInfo::json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl BankInfo {
+    fn json(&self) -> Value {
+        json!({
+            "path": self.path,
+            "events": self.events.iter().map(EventInfo::json).collect::<Vec<_>>(),
+            "snapshots": self.snapshots.iter().map(EventInfo::json).collect::<Vec<_>>(),
+            "buses": self.buses,
+            "vcas": self.vcas,
+        })
+    }
+}
+
+impl EventInfo {
+    fn read(event: &CatalogEvent) -> Result<Self, String> {
+        let description = event.description();
+
+        let user_properties = event
+            .user_properties()
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    UserPropertyValue::Integer(value) => json!(value),
+                    UserPropertyValue::Boolean(value) => json!(value),
+                    UserPropertyValue::Float(value) => json!(value),
+                    UserPropertyValue::String(value) => json!(value),
+                };
+                (name.clone(), value)
+            })
+            .collect();
+
+        let parameters = event
+            .parameters()
+            .iter()
+            .enumerate()
+            .map(|(index, parameter)| {
+                ParameterInfo::read(parameter, |label| {
+                    let (_, size) =
+                        description.get_parameter_label_by_index(index as i32, label, 0)?;
+                    description
+                        .get_parameter_label_by_index(index as i32, label, size)
+                        .map(|(label, _)| label)
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(EventInfo {
+            path: event.path().to_string(),
+            is_3d: event.is_3d(),
+            is_oneshot: event.is_oneshot(),
+            is_stream: event.is_stream(),
+            length: event.length(),
+            min_distance: event.min_distance(),
+            max_distance: event.max_distance(),
+            user_properties,
+            parameters,
+        })
+    }
+
+    fn lines(&self, lines: &mut Vec<Line>) {
+        let mut details = format!(" ({}", if self.is_3d { "3D" } else { "2D" });
+        if self.is_oneshot {
+            details.push_str(", one-shot");
+        }
+        if self.is_stream {
+            details.push_str(", streamed");
+        }
+        if self.length > 0 {
+            let _ = write!(details, ", {} ms", self.length);
+        }
+        if self.is_3d {
+            let _ = write!(
+                details,
+                ", distance {}..{}",
+                self.min_distance, self.max_distance
+            );
+        }
+        details.push(')');
+
+        lines.push((1, self.path.clone(), details));
+
+        for parameter in &self.parameters {
+            lines.push(parameter.line(2));
+        }
+        for (name, value) in &self.user_properties {
+            lines.push((2, format!("property {name}"), format!(" = {value}")));
+        }
+    }
+
+    /// Distances are `null` if they are infinite, as JSON has no representation for infinity.
+    fn json(&self) -> Value {
+        let user_properties: Map<String, Value> = self.user_properties.iter().cloned().collect();
+
+        json!({
+            "path": self.path,
+            "is_3d": self.is_3d,
+            "is_oneshot": self.is_oneshot,
+            "is_stream": self.is_stream,
+            "length": self.length,
+            "min_distance": self.min_distance,
+            "max_distance": self.max_distance,
+            "user_properties": user_properties,
+            "parameters": self.parameters.iter().map(ParameterInfo::json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl ParameterInfo {
+    fn read<E: std::fmt::Display>(
+        description: &ParameterDescription,
+        label: impl Fn(i32) -> Result<String, E>,
+    ) -> Result<Self, String> {
+        let flag_names = [
+            (FMOD_STUDIO_PARAMETER_READONLY, "read-only"),
+            (FMOD_STUDIO_PARAMETER_AUTOMATIC, "automatic"),
+            (FMOD_STUDIO_PARAMETER_GLOBAL, "global"),
+            (FMOD_STUDIO_PARAMETER_DISCRETE, "discrete"),
+            (FMOD_STUDIO_PARAMETER_LABELED, "labeled"),
+        ];
+
+        let labels = (0..label_count(description))
+            .map(|index| label(index).map_err(|e| e.to_string()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(ParameterInfo {
+            name: description.name.clone(),
+            minimum: description.minimum,
+            maximum: description.maximum,
+            default_value: description.defaultvalue,
+            flags: flag_names
+                .into_iter()
+                .filter(|(flag, _)| description.flags & flag != 0)
+                .map(|(_, name)| name)
+                .collect(),
+            labels,
+        })
+    }
+
+    fn line(&self, depth: usize) -> Line {
+        let mut details = format!(
+            " [{}..{}, default {}]",
+            self.minimum, self.maximum, self.default_value
+        );
+        if !self.flags.is_empty() {
+            let _ = write!(details, " {}", self.flags.join(", "));
+        }
+        if !self.labels.is_empty() {
+            let _ = write!(details, " labels: {}", self.labels.join(", "));
+        }
+
+        (depth, format!("parameter {}", self.name), details)
+    }
+
+    fn json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "minimum": self.minimum,
+            "maximum": self.maximum,
+            "default": self.default_value,
+            "flags": self.flags,
+            "labels": self.labels,
+        })
+    }
+}
+
+/// Adds the path if it is a file, or all `.bank` files in it if it is a directory.
+fn collect_banks(path: &Path, banks: &mut Vec<String>) -> Result<(), String> {
+    if !path.is_dir() {
+        banks.push(path.to_string_lossy().into_owned());
+        return Ok(());
+    }
+
+    let entries =
+        std::fs::read_dir(path).map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+
+    let mut found = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        let path = entry.path();
+
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "bank")
+        {
+            found.push(path.to_string_lossy().into_owned());
+        }
+    }
+
+    // Load the strings bank first, so paths are known as soon as possible.
+    found.sort_by_key(|path| (!path.ends_with(".strings.bank"), path.clone()));
+    banks.extend(found);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameter(name: &str) -> ParameterInfo {
+        ParameterInfo {
+            name: name.to_string(),
+            minimum: 0.0,
+            maximum: 1.0,
+            default_value: 0.0,
+            flags: Vec::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    fn event(path: &str, parameters: Vec<ParameterInfo>) -> EventInfo {
+        EventInfo {
+            path: path.to_string(),
+            is_3d: true,
+            is_oneshot: false,
+            is_stream: false,
+            length: 0,
+            min_distance: 1.0,
+            max_distance: f32::INFINITY,
+            user_properties: vec![("Note".to_string(), json!("say \"hi\"\n"))],
+            parameters,
+        }
+    }
+
+    fn build(events: Vec<EventInfo>) -> Build {
+        Build {
+            banks: vec![BankInfo {
+                path: "Master.bank".to_string(),
+                events,
+                snapshots: Vec::new(),
+                buses: vec!["bus:/".to_string()],
+                vcas: Vec::new(),
+            }],
+            parameters: vec![parameter("Weather")],
+        }
+    }
+
+    #[test]
+    fn keys_lines_by_their_ancestors() {
+        let lines = build(vec![event("event:/Car", vec![parameter("RPM")])]).keyed_lines();
+
+        let keys: Vec<&str> = lines.keys().map(String::as_str).collect();
+        assert_eq!(
+            keys,
+            [
+                "Global parameters",
+                "Global parameters > parameter Weather",
+                "Master.bank",
+                "Master.bank > bus:/",
+                "Master.bank > event:/Car",
+                "Master.bank > event:/Car > parameter RPM",
+                "Master.bank > event:/Car > property Note",
+            ]
+        );
+        assert_eq!(
+            lines["Master.bank > event:/Car > parameter RPM"],
+            " [0..1, default 0]"
+        );
+    }
+
+    #[test]
+    fn diff_lists_removed_added_and_changed_lines() {
+        let mut changed = event("event:/Car", Vec::new());
+        changed.is_3d = false;
+
+        let old = build(vec![event("event:/Car", vec![parameter("RPM")])]).keyed_lines();
+        let new = build(vec![changed, event("event:/Horn", Vec::new())]).keyed_lines();
+        let diff = Diff::compare(&old, &new);
+
+        assert_eq!(diff.removed, ["Master.bank > event:/Car > parameter RPM"]);
+        assert_eq!(
+            diff.added,
+            [
+                "Master.bank > event:/Horn",
+                "Master.bank > event:/Horn > property Note"
+            ]
+        );
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].0, "Master.bank > event:/Car");
+        assert!(!diff.is_empty());
+
+        assert_eq!(
+            diff.json()["changed"][0],
+            json!({
+                "item": "Master.bank > event:/Car",
+                "old": "(3D, distance 1..inf)",
+                "new": "(2D)",
+            })
+        );
+    }
+
+    #[test]
+    fn identical_builds_have_no_diff() {
+        let old = build(vec![event("event:/Car", Vec::new())]).keyed_lines();
+        let new = build(vec![event("event:/Car", Vec::new())]).keyed_lines();
+
+        assert!(Diff::compare(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn json_escapes_strings_and_omits_infinite_distances() {
+        let json = build(vec![event("event:/Car \"Sport\"", Vec::new())])
+            .json()
+            .to_string();
+
+        assert!(json.contains(r#""path":"event:/Car \"Sport\"""#), "{json}");
+        assert!(
+            json.contains(r#""user_properties":{"Note":"say \"hi\"\n"}"#),
+            "{json}"
+        );
+        assert!(json.contains(r#""max_distance":null"#), "{json}");
+        assert!(json.contains(r#""min_distance":1.0"#), "{json}");
+    }
+}
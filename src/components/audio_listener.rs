@@ -1,23 +1,113 @@
 use bevy::ecs::error::Result;
+use bevy::log::warn;
 use bevy::math::Vec3;
-use bevy::prelude::{Component, GlobalTransform, Query, Res, With};
+use bevy::prelude::{Component, Entity, GlobalTransform, Local, Query, RemovedComponents, Res};
 
 use crate::attributes_3d::attributes3d;
 use crate::components::velocity::Velocity;
 use crate::fmod_studio::FmodStudio;
 
+/// The number of listeners FMOD supports.
+const MAX_LISTENERS: usize = 8;
+
 /// Component that represents an audio listener in 3D space.
 ///
+/// Every listener entity is assigned an FMOD listener index, which stays the same for as long as
+/// the entity is a listener, even if the component is replaced. The index of a removed listener is
+/// muted with a weight of `0.0` until a new listener takes it over. FMOD supports up to eight
+/// listeners, e.g. one per player for split-screen.
+///
+/// A listener with a [`weight`](Self::weight) of `0.0` doesn't contribute to the mix, so changing
+/// the weights over time crossfades between cameras.
+///
 /// See the [`Velocity`] component for information on enabling the Doppler effect.
-#[derive(Component, Default)]
-pub struct AudioListener;
+#[derive(Component, Debug, Clone)]
+pub struct AudioListener {
+    /// How much the listener contributes to the mix, from `0.0` to `1.0`. Values outside of this
+    /// range are clamped. Defaults to `1.0`.
+    pub weight: f32,
+    index: Option<usize>,
+}
+
+impl Default for AudioListener {
+    fn default() -> Self {
+        AudioListener {
+            weight: 1.0,
+            index: None,
+        }
+    }
+}
 
 impl AudioListener {
+    /// Creates a listener with the given weight.
+    pub fn new(weight: f32) -> Self {
+        AudioListener {
+            weight,
+            index: None,
+        }
+    }
+
+    /// The FMOD listener index, once the listener was picked up by the plugin. `None` if there are
+    /// more listeners than FMOD supports.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
     pub(crate) fn update_3d_attributes(
-        query: Query<(&GlobalTransform, Option<&Velocity>), With<AudioListener>>,
+        mut query: Query<(
+            Entity,
+            &mut AudioListener,
+            &GlobalTransform,
+            Option<&Velocity>,
+        )>,
+        mut removed: RemovedComponents<AudioListener>,
+        mut slots: Local<ListenerSlots>,
+        mut ignored_before: Local<usize>,
         studio: Res<FmodStudio>,
     ) -> Result {
-        if let Ok((transform, vel_component)) = query.single() {
+        let count = slots.len();
+        let mut freed = Vec::new();
+
+        for entity in removed.read() {
+            freed.extend(slots.remove(entity));
+        }
+
+        let mut ignored = 0;
+
+        for (entity, mut listener, _, _) in query.iter_mut() {
+            // Replacing the component resets its index, but not the slot of the entity.
+            let index = slots.assign(entity);
+
+            if listener.index != index {
+                listener.index = index;
+            }
+            if index.is_none() {
+                ignored += 1;
+            }
+        }
+
+        if ignored > *ignored_before {
+            warn!("FMOD supports at most {MAX_LISTENERS} listeners, ignoring {ignored} of them");
+        }
+        *ignored_before = ignored;
+
+        // FMOD always has at least one listener.
+        if slots.len() != count && slots.len() > 0 {
+            studio.set_num_listeners(slots.len() as i32)?;
+        }
+
+        // Free indices below the last listener keep their last position, so mute them.
+        for index in freed {
+            if index < slots.len() && !slots.is_assigned(index) {
+                studio.set_listener_weight(index as i32, 0.0)?;
+            }
+        }
+
+        for (_, listener, transform, vel_component) in query.iter() {
+            let Some(index) = listener.index else {
+                continue;
+            };
+
             let mut velocity = Vec3::ZERO;
 
             if let Some(vel_component) = vel_component {
@@ -25,7 +115,7 @@ impl AudioListener {
             }
 
             studio.set_listener_attributes(
-                0,
+                index as i32,
                 attributes3d(
                     transform.translation(),
                     velocity,
@@ -34,8 +124,152 @@ impl AudioListener {
                 ),
                 None,
             )?;
+            studio.set_listener_weight(index as i32, listener.weight.clamp(0.0, 1.0))?;
         }
 
         Ok(())
     }
 }
+
+/// The entities of the FMOD listeners, by listener index. Indices of removed listeners are free
+/// until a new listener takes them.
+#[derive(Debug, Default)]
+pub(crate) struct ListenerSlots(Vec<Option<Entity>>);
+
+impl ListenerSlots {
+    /// The number of FMOD listeners needed, up to the last assigned index.
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_assigned(&self, index: usize) -> bool {
+        self.0.get(index).is_some_and(Option::is_some)
+    }
+
+    fn position(&self, entity: Entity) -> Option<usize> {
+        self.0.iter().position(|listener| *listener == Some(entity))
+    }
+
+    /// Frees the slot of the entity and returns its index. The indices of other listeners stay
+    /// the same.
+    fn remove(&mut self, entity: Entity) -> Option<usize> {
+        let index = self.position(entity)?;
+        self.0[index] = None;
+
+        while self.0.last().is_some_and(Option::is_none) {
+            self.0.pop();
+        }
+
+        Some(index)
+    }
+
+    /// The index of the entity, assigning the lowest free one to new listeners. `None` if all
+    /// indices are taken.
+    fn assign(&mut self, entity: Entity) -> Option<usize> {
+        if let Some(index) = self.position(entity) {
+            return Some(index);
+        }
+
+        if let Some(index) = self.0.iter().position(Option::is_none) {
+            self.0[index] = Some(entity);
+            return Some(index);
+        }
+
+        if self.0.len() == MAX_LISTENERS {
+            return None;
+        }
+
+        self.0.push(Some(entity));
+        Some(self.0.len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::World;
+
+    use super::*;
+
+    fn entities(count: usize) -> Vec<Entity> {
+        let mut world = World::new();
+        (0..count).map(|_| world.spawn_empty().id()).collect()
+    }
+
+    #[test]
+    fn indices_stay_the_same_while_assigned() {
+        let entities = entities(2);
+        let (a, b) = (entities[0], entities[1]);
+        let mut slots = ListenerSlots::default();
+
+        assert_eq!(slots.assign(a), Some(0));
+        assert_eq!(slots.assign(b), Some(1));
+
+        // A replaced component asks for the index of its entity again.
+        assert_eq!(slots.assign(a), Some(0));
+        assert_eq!(slots.assign(b), Some(1));
+        assert_eq!(slots.len(), 2);
+    }
+
+    #[test]
+    fn removed_indices_are_reused_without_moving_other_listeners() {
+        let entities = entities(4);
+        let (a, b, c, d) = (entities[0], entities[1], entities[2], entities[3]);
+        let mut slots = ListenerSlots::default();
+        for entity in [a, b, c] {
+            slots.assign(entity);
+        }
+
+        assert_eq!(slots.remove(a), Some(0));
+        assert_eq!(slots.assign(b), Some(1));
+        assert_eq!(slots.assign(c), Some(2));
+        assert_eq!(slots.len(), 3);
+        assert!(!slots.is_assigned(0));
+
+        // A new listener takes the free index.
+        assert_eq!(slots.assign(d), Some(0));
+        assert!(slots.is_assigned(0));
+
+        // Removing an entity without a slot changes nothing.
+        assert_eq!(slots.remove(a), None);
+        assert_eq!(slots.len(), 3);
+    }
+
+    #[test]
+    fn trailing_free_indices_are_dropped() {
+        let entities = entities(3);
+        let (a, b, c) = (entities[0], entities[1], entities[2]);
+        let mut slots = ListenerSlots::default();
+        for entity in [a, b, c] {
+            slots.assign(entity);
+        }
+
+        slots.remove(b);
+        assert_eq!(slots.len(), 3);
+
+        slots.remove(c);
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots.assign(a), Some(0));
+    }
+
+    #[test]
+    fn listeners_beyond_the_limit_wait_for_a_free_index() {
+        let entities = entities(MAX_LISTENERS + 1);
+        let mut slots = ListenerSlots::default();
+
+        for (index, entity) in entities.iter().enumerate().take(MAX_LISTENERS) {
+            assert_eq!(slots.assign(*entity), Some(index));
+        }
+
+        let overflow = entities[MAX_LISTENERS];
+        assert_eq!(slots.assign(overflow), None);
+        assert_eq!(slots.len(), MAX_LISTENERS);
+
+        slots.remove(entities[2]);
+        assert_eq!(
+            slots.assign(entities[MAX_LISTENERS - 1]),
+            Some(MAX_LISTENERS - 1)
+        );
+        assert_eq!(slots.assign(overflow), Some(2));
+        assert_eq!(slots.assign(entities[0]), Some(0));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::attributes_3d::attributes3d;
+use crate::components::playback_status::FmodPlaybackStatus;
 use crate::components::velocity::Velocity;
 use bevy::ecs::error::Result;
 use bevy::math::Vec3;
@@ -7,6 +8,7 @@ use libfmod::{EventInstance, StopMode};
 
 /// See the [`Velocity`] component for information on enabling the Doppler effect.
 #[derive(Component, Deref, DerefMut)]
+#[require(FmodPlaybackStatus)]
 pub struct AudioSource {
     /// The [EventInstance] that is playing the audio. Create an instance from an
     /// [EventDescription](libfmod::EventDescription) using
@@ -22,6 +24,11 @@ impl AudioSource {
         mut query: Query<(&AudioSource, &GlobalTransform, Option<&Velocity>)>,
     ) -> Result {
         for (audio_source, transform, vel_component) in query.iter_mut() {
+            // The instance is invalidated when its bank is unloaded.
+            if !audio_source.is_valid() {
+                continue;
+            }
+
             let mut velocity = Vec3::ZERO;
 
             if let Some(vel_component) = vel_component {
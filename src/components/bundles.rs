@@ -55,3 +55,22 @@ pub struct SpatialListenerBundle {
     velocity: Velocity,
     transform: Transform,
 }
+
+impl SpatialListenerBundle {
+    /// Sets how much the listener contributes to the mix, see [`AudioListener::weight`].
+    #[must_use]
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.audio_listener.weight = weight;
+        self
+    }
+}
+
+impl From<AudioListener> for SpatialListenerBundle {
+    fn from(value: AudioListener) -> Self {
+        SpatialListenerBundle {
+            audio_listener: value,
+            velocity: Velocity::default(),
+            transform: Transform::default(),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::ecs::error::Result;
+use bevy::prelude::{Commands, Component, Entity, EntityCommands, EntityWorldMut, Query, With};
+use libfmod::{PlaybackState, StopMode};
+
+use crate::components::audio_source::AudioSource;
+
+/// Marks an entity that was despawned with
+/// [`despawn_with_fadeout`](DespawnWithFadeoutExt::despawn_with_fadeout) and is waiting for its
+/// event to fade out.
+///
+/// The entity keeps its [`AudioSource`] and transform, so the fadeout still follows it, but it
+/// should no longer take part in gameplay. Exclude it from your queries with `Without<FadingOut>`.
+#[derive(Component, Debug)]
+pub struct FadingOut;
+
+impl FadingOut {
+    pub(crate) fn despawn_stopped(
+        mut commands: Commands,
+        query: Query<(Entity, Option<&AudioSource>), With<FadingOut>>,
+    ) -> Result {
+        for (entity, audio_source) in query.iter() {
+            let stopped = match audio_source {
+                Some(audio_source) if audio_source.is_valid() => {
+                    audio_source.get_playback_state()? == PlaybackState::Stopped
+                }
+                _ => true,
+            };
+
+            if stopped {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Extension trait for despawning entities once their event faded out.
+pub trait DespawnWithFadeoutExt {
+    /// Stops the event of the entity's [`AudioSource`] with [`StopMode::AllowFadeout`] and
+    /// despawns the entity once the event actually stopped.
+    ///
+    /// Until then, the entity is marked with [`FadingOut`] and its audio keeps following it.
+    /// Entities without an [`AudioSource`] are despawned right away.
+    fn despawn_with_fadeout(&mut self);
+}
+
+impl DespawnWithFadeoutExt for EntityCommands<'_> {
+    fn despawn_with_fadeout(&mut self) {
+        self.queue(|mut entity: EntityWorldMut| -> Result {
+            if let Some(audio_source) = entity.get::<AudioSource>()
+                && audio_source.is_valid()
+            {
+                audio_source.stop(StopMode::AllowFadeout)?;
+                entity.insert(FadingOut);
+            } else {
+                entity.despawn();
+            }
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::{Commands, Component, MessageReader, Query, ReflectComponent, With};
+use bevy::reflect::Reflect;
+
+use crate::components::audio_source::AudioSource;
+use crate::event_callbacks::{FmodCallback, FmodCallbackKind};
+
+/// What happens to an entity with an [`AudioSource`] once its event finished playing.
+///
+/// An event counts as finished when FMOD reports that it stopped, either because it ended on its
+/// own or because it was stopped, or that it failed to start. Looping events only finish when they
+/// are stopped. This is driven by the [`FmodCallback`]s of the event instance, so events that
+/// start and stop within a single frame are not missed.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::prelude::*;
+/// fn spawn_explosion(mut commands: Commands) {
+///     commands.spawn((
+///         FmodEvent::new("event:/Weapons/Explosion").with_autoplay(true),
+///         FinishBehavior::Despawn,
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum FinishBehavior {
+    /// Keep the entity and its [`AudioSource`], so the event can be started again.
+    #[default]
+    Keep,
+    /// Despawn the entity.
+    Despawn,
+    /// Remove the [`AudioSource`] from the entity, which releases the event instance.
+    Remove,
+}
+
+impl FinishBehavior {
+    pub(crate) fn apply(
+        mut commands: Commands,
+        mut callbacks: MessageReader<FmodCallback>,
+        query: Query<&FinishBehavior, With<AudioSource>>,
+    ) {
+        let mut finished = EntityHashSet::default();
+
+        for callback in callbacks.read() {
+            // Events that failed to start, e.g. because of polyphony limits, never play.
+            if !matches!(
+                callback.kind,
+                FmodCallbackKind::Stopped | FmodCallbackKind::StartFailed
+            ) {
+                continue;
+            }
+
+            let Ok(finish_behavior) = query.get(callback.entity) else {
+                continue;
+            };
+
+            if !finished.insert(callback.entity) {
+                continue;
+            }
+
+            match finish_behavior {
+                FinishBehavior::Keep => {}
+                FinishBehavior::Despawn => commands.entity(callback.entity).despawn(),
+                FinishBehavior::Remove => {
+                    commands.entity(callback.entity).remove::<AudioSource>();
+                }
+            }
+        }
+    }
+}
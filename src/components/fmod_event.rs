@@ -0,0 +1,128 @@
+use bevy::ecs::error::Result;
+use bevy::ecs::lifecycle::Insert;
+use bevy::ecs::observer::On;
+use bevy::log::warn;
+use bevy::prelude::{
+    Commands, Component, Entity, Message, MessageWriter, Query, ReflectComponent, Res, Transform,
+};
+use bevy::reflect::Reflect;
+use libfmod::StopMode;
+
+use crate::components::audio_source::AudioSource;
+use crate::components::velocity::Velocity;
+use crate::fmod_studio::FmodStudio;
+use crate::reflect_remote::StopModeRemote;
+
+/// Plays an FMOD event by its path, without having to create the event instance yourself.
+///
+/// When this component is inserted, the event is looked up and an [`AudioSource`] with a new
+/// event instance is inserted on the same entity. If the event can't be found, an
+/// [`FmodEventLookupFailed`] message is sent instead. The component can be used in scenes.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::prelude::*;
+/// fn spawn_forest(mut commands: Commands) {
+///     commands.spawn((
+///         FmodEvent::new("event:/Ambience/Forest").with_autoplay(true),
+///         Transform::from_xyz(10.0, 0.0, 0.0),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+#[require(Transform, Velocity)]
+pub struct FmodEvent {
+    /// The path of the event, e.g. `event:/Ambience/Forest`.
+    pub path: String,
+    /// Whether the event starts playing as soon as its instance is created.
+    pub autoplay: bool,
+    /// The [`StopMode`] to use when the entity despawns.
+    #[reflect(remote = StopModeRemote)]
+    pub despawn_stop_mode: StopMode,
+}
+
+/// Sent when the event of an [`FmodEvent`] could not be found or instantiated.
+#[derive(Message, Debug, Clone)]
+pub struct FmodEventLookupFailed {
+    /// The entity the [`FmodEvent`] was inserted on.
+    pub entity: Entity,
+    /// The path of the event.
+    pub path: String,
+    /// Why the event could not be instantiated.
+    pub reason: String,
+}
+
+impl FmodEvent {
+    /// Creates an event that does not play automatically and fades out when the entity despawns.
+    pub fn new(path: impl Into<String>) -> Self {
+        FmodEvent {
+            path: path.into(),
+            autoplay: false,
+            despawn_stop_mode: StopMode::AllowFadeout,
+        }
+    }
+
+    /// Sets whether the event starts playing as soon as its instance is created.
+    #[must_use]
+    pub fn with_autoplay(mut self, autoplay: bool) -> Self {
+        self.autoplay = autoplay;
+        self
+    }
+
+    /// Sets the [`StopMode`] to use when the entity despawns.
+    #[must_use]
+    pub fn with_despawn_stop_mode(mut self, despawn_stop_mode: StopMode) -> Self {
+        self.despawn_stop_mode = despawn_stop_mode;
+        self
+    }
+
+    pub(crate) fn on_insert(
+        insert: On<Insert, FmodEvent>,
+        query: Query<&FmodEvent>,
+        studio: Option<Res<FmodStudio>>,
+        mut commands: Commands,
+        mut lookup_failed: MessageWriter<FmodEventLookupFailed>,
+    ) -> Result {
+        // Without FMOD there is nothing to play.
+        let Some(studio) = studio else {
+            return Ok(());
+        };
+
+        let fmod_event = query.get(insert.entity)?;
+
+        let event_instance = match studio
+            .get_event(&fmod_event.path)
+            .and_then(|description| description.create_instance())
+        {
+            Ok(event_instance) => event_instance,
+            Err(e) => {
+                warn!(
+                    "Could not create event instance of {}: {e}",
+                    fmod_event.path
+                );
+                lookup_failed.write(FmodEventLookupFailed {
+                    entity: insert.entity,
+                    path: fmod_event.path.clone(),
+                    reason: e.to_string(),
+                });
+                return Ok(());
+            }
+        };
+
+        if fmod_event.autoplay
+            && let Err(e) = event_instance.start()
+        {
+            // The instance would leak, as no AudioSource takes ownership of it.
+            event_instance.release()?;
+            return Err(e.into());
+        }
+
+        commands.entity(insert.entity).insert(AudioSource {
+            event_instance,
+            despawn_stop_mode: fmod_event.despawn_stop_mode,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+use bevy::log::warn;
+use bevy::prelude::{Changed, Component, DetectChangesMut, MessageReader, Or, Query};
+use libfmod::{EventDescription, EventInstance, Guid, ParameterId};
+
+use crate::bank_loading::BankLoaded;
+use crate::components::audio_source::AudioSource;
+use crate::ids::{guids_equal, parameter_ids_equal};
+
+/// Local parameters of the event instance of the [`AudioSource`] on the same entity.
+///
+/// The values are sent to FMOD whenever this component changes or the audio source is replaced,
+/// instead of calling `set_parameter_by_name` by hand. Values are clamped to the range of the
+/// parameter. Parameters the event does not have are skipped with a warning.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::prelude::*;
+/// fn spawn_forest(mut commands: Commands) {
+///     commands.spawn((
+///         FmodEvent::new("event:/Ambience/Forest").with_autoplay(true),
+///         FmodParameters::new().with_parameter("Rain", 0.0),
+///     ));
+/// }
+///
+/// fn start_rain(mut query: Query<&mut FmodParameters>) {
+///     for mut parameters in &mut query {
+///         parameters.set("Rain", 1.0);
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default)]
+pub struct FmodParameters {
+    /// Whether parameters jump to their new value instead of moving towards it with the seek
+    /// speed set in FMOD Studio. Defaults to `false`.
+    pub ignore_seek_speed: bool,
+    values: Vec<(ParameterKey, f32)>,
+    event_id: Option<Guid>,
+    resolved: Vec<(ParameterKey, Option<ResolvedParameter>)>,
+}
+
+/// Identifies a parameter of an event, either by its name or its [`ParameterId`].
+///
+/// Looking up a name is only done once per event, so both are equally fast.
+#[derive(Debug, Clone)]
+pub enum ParameterKey {
+    /// The name of the parameter as shown in FMOD Studio.
+    Name(String),
+    /// The ID of the parameter.
+    Id(ParameterId),
+}
+
+impl PartialEq for ParameterKey {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ParameterKey::Name(a), ParameterKey::Name(b)) => a == b,
+            (ParameterKey::Id(a), ParameterKey::Id(b)) => parameter_ids_equal(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl From<&str> for ParameterKey {
+    fn from(name: &str) -> Self {
+        ParameterKey::Name(name.to_string())
+    }
+}
+
+impl From<String> for ParameterKey {
+    fn from(name: String) -> Self {
+        ParameterKey::Name(name)
+    }
+}
+
+impl From<ParameterId> for ParameterKey {
+    fn from(id: ParameterId) -> Self {
+        ParameterKey::Id(id)
+    }
+}
+
+/// Parameters need to be sent to FMOD when they changed or the event instance was replaced.
+type ParametersOutdated = Or<(Changed<FmodParameters>, Changed<AudioSource>)>;
+
+/// A parameter of an event, looked up once so its value can be set by ID.
+#[derive(Debug, Clone)]
+pub(crate) struct ResolvedParameter {
+    id: ParameterId,
+    minimum: f32,
+    maximum: f32,
+}
+
+impl ResolvedParameter {
+    /// Looks up the parameter of the event.
+    pub(crate) fn resolve(
+        description: EventDescription,
+        key: &ParameterKey,
+    ) -> crate::Result<Self> {
+        let parameter = match key {
+            ParameterKey::Name(name) => description.get_parameter_description_by_name(name)?,
+            ParameterKey::Id(id) => description.get_parameter_description_by_id(id.clone())?,
+        };
+
+        Ok(ResolvedParameter {
+            id: parameter.id,
+            minimum: parameter.minimum,
+            maximum: parameter.maximum,
+        })
+    }
+
+    /// Sets the value of the parameter on an instance of the event, clamped to its range.
+    pub(crate) fn set(
+        &self,
+        event_instance: EventInstance,
+        value: f32,
+        ignore_seek_speed: bool,
+    ) -> crate::Result<()> {
+        event_instance.set_parameter_by_id(
+            self.id.clone(),
+            value.clamp(self.minimum, self.maximum),
+            ignore_seek_speed,
+        )?;
+
+        Ok(())
+    }
+}
+
+impl FmodParameters {
+    /// Creates an empty set of parameters.
+    pub fn new() -> Self {
+        FmodParameters::default()
+    }
+
+    /// Sets the initial value of a parameter.
+    #[must_use]
+    pub fn with_parameter(mut self, key: impl Into<ParameterKey>, value: f32) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Sets whether parameters jump to their new value, ignoring the seek speed.
+    #[must_use]
+    pub fn with_ignore_seek_speed(mut self, ignore_seek_speed: bool) -> Self {
+        self.ignore_seek_speed = ignore_seek_speed;
+        self
+    }
+
+    /// Sets the value of a parameter. It is sent to FMOD at the end of the frame.
+    pub fn set(&mut self, key: impl Into<ParameterKey>, value: f32) {
+        let key = key.into();
+
+        match self
+            .values
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.values.push((key, value)),
+        }
+    }
+
+    /// The value of a parameter as last set, before clamping.
+    pub fn get(&self, key: impl Into<ParameterKey>) -> Option<f32> {
+        let key = key.into();
+
+        self.values
+            .iter()
+            .find(|(existing, _)| *existing == key)
+            .map(|(_, value)| *value)
+    }
+
+    /// Removes a parameter. The event instance keeps its current value.
+    pub fn remove(&mut self, key: impl Into<ParameterKey>) -> Option<f32> {
+        let key = key.into();
+        let index = self
+            .values
+            .iter()
+            .position(|(existing, _)| *existing == key)?;
+
+        Some(self.values.remove(index).1)
+    }
+
+    /// Iterates over all parameters and their values.
+    pub fn iter(&self) -> impl Iterator<Item = (&ParameterKey, f32)> {
+        self.values.iter().map(|(key, value)| (key, *value))
+    }
+
+    pub(crate) fn sync(
+        mut query: Query<(&AudioSource, &mut FmodParameters), ParametersOutdated>,
+        mut bank_loaded: MessageReader<BankLoaded>,
+    ) {
+        // A reloaded bank may assign new IDs to the parameters of its events.
+        let banks_loaded = bank_loaded.read().count() > 0;
+
+        for (audio_source, mut parameters) in query.iter_mut() {
+            if !audio_source.is_valid() {
+                continue;
+            }
+
+            // Caching the parameter IDs must not count as a change.
+            let parameters = parameters.bypass_change_detection();
+
+            if banks_loaded {
+                parameters.resolved.clear();
+            }
+
+            if let Err(e) = parameters.apply(audio_source.event_instance) {
+                warn!("Could not set the parameters of an event instance: {e}");
+            }
+        }
+    }
+
+    fn apply(&mut self, event_instance: EventInstance) -> crate::Result<()> {
+        let description = event_instance.get_description()?;
+        let event_id = description.get_id()?;
+
+        // The cached IDs belong to another event if the instance was replaced.
+        if !self
+            .event_id
+            .as_ref()
+            .is_some_and(|cached| guids_equal(cached, &event_id))
+        {
+            self.event_id = Some(event_id);
+            self.resolved.clear();
+        }
+
+        for (key, value) in &self.values {
+            let index = match self
+                .resolved
+                .iter()
+                .position(|(resolved, _)| resolved == key)
+            {
+                Some(index) => index,
+                None => {
+                    let resolved = ResolvedParameter::resolve(description, key)
+                        .inspect_err(|e| warn!("Event has no parameter {key:?}: {e}"))
+                        .ok();
+                    self.resolved.push((key.clone(), resolved));
+                    self.resolved.len() - 1
+                }
+            };
+
+            let Some(parameter) = &self.resolved[index].1 else {
+                continue;
+            };
+
+            if let Err(e) = parameter.set(event_instance, *value, self.ignore_seek_speed) {
+                warn!("Could not set parameter {key:?} to {value}: {e}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(data_1: u32, data_2: u32) -> ParameterId {
+        ParameterId { data_1, data_2 }
+    }
+
+    #[test]
+    fn keys_compare_by_name_or_id() {
+        assert_eq!(ParameterKey::from("Rain"), ParameterKey::from("Rain"));
+        assert_ne!(ParameterKey::from("Rain"), ParameterKey::from("Wind"));
+        assert_eq!(ParameterKey::from(id(1, 2)), ParameterKey::from(id(1, 2)));
+        assert_ne!(ParameterKey::from(id(1, 2)), ParameterKey::from(id(2, 1)));
+        assert_ne!(ParameterKey::from("Rain"), ParameterKey::from(id(1, 2)));
+    }
+
+    #[test]
+    fn set_replaces_existing_values() {
+        let mut parameters = FmodParameters::new()
+            .with_parameter("Rain", 0.0)
+            .with_parameter(id(1, 2), 0.5);
+
+        parameters.set("Rain", 1.0);
+        parameters.set(id(1, 2), 0.25);
+
+        assert_eq!(parameters.iter().count(), 2);
+        assert_eq!(parameters.get("Rain"), Some(1.0));
+        assert_eq!(parameters.get(id(1, 2)), Some(0.25));
+        assert_eq!(parameters.remove("Rain"), Some(1.0));
+        assert_eq!(parameters.get("Rain"), None);
+    }
+}
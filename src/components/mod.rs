@@ -10,6 +10,22 @@ pub mod audio_listener;
 pub mod audio_source;
 pub mod bundles;
 #[doc(hidden)]
+pub mod fading_out;
+#[doc(hidden)]
+pub mod finish_behavior;
+#[doc(hidden)]
+pub mod fmod_event;
+#[doc(hidden)]
+pub mod fmod_parameters;
+#[doc(hidden)]
+pub mod music_clock;
+#[doc(hidden)]
+pub mod parameter_bindings;
+#[doc(hidden)]
+pub mod parameter_tweens;
+#[doc(hidden)]
+pub mod playback_status;
+#[doc(hidden)]
 pub mod velocity;
 
 #[doc(inline)]
@@ -17,4 +33,22 @@ pub use audio_listener::AudioListener;
 #[doc(inline)]
 pub use audio_source::AudioSource;
 #[doc(inline)]
+pub use fading_out::{DespawnWithFadeoutExt, FadingOut};
+#[doc(inline)]
+pub use finish_behavior::FinishBehavior;
+#[doc(inline)]
+pub use fmod_event::{FmodEvent, FmodEventLookupFailed};
+#[doc(inline)]
+pub use fmod_parameters::{FmodParameters, ParameterKey};
+#[doc(inline)]
+pub use music_clock::{MusicClock, on_beat};
+#[doc(inline)]
+pub use parameter_bindings::{ParameterBinding, ParameterBindings};
+#[doc(inline)]
+pub use parameter_tweens::{
+    ParameterTween, ParameterTweenCompleted, ParameterTweens, TweenTarget, TweenTime,
+};
+#[doc(inline)]
+pub use playback_status::FmodPlaybackStatus;
+#[doc(inline)]
 pub use velocity::Velocity;
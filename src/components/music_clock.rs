@@ -0,0 +1,339 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::ecs::error::Result;
+use bevy::platform::time::Instant;
+use bevy::prelude::{Component, Entity, Local, MessageReader, Query, Res};
+use bevy::time::{Real, Time};
+
+use crate::event_callbacks::{FmodCallback, FmodCallbackKind};
+use crate::fmod_studio::FmodStudio;
+
+/// Follows the beats of the event playing on this entity's
+/// [`AudioSource`](crate::prelude::AudioSource) in game time.
+///
+/// Beats are taken from the tempo markers of the event's timeline. FMOD reports a beat when it
+/// mixes it, which is a while before it can be heard. The clock delays every beat by the output
+/// latency of the DSP buffers plus [`latency_offset`](Self::latency_offset), so gameplay lines up
+/// with what the player hears.
+///
+/// FMOD does not report the latency of the output device or the operating system's mixer, so it
+/// is not compensated automatically. Add it to the [`latency_offset`](Self::latency_offset), e.g.
+/// from a calibration screen.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::prelude::*;
+/// let mut app = App::new();
+///
+/// let music = app
+///     .world_mut()
+///     .spawn((
+///         FmodEvent::new("event:/Music/Level 01").with_autoplay(true),
+///         MusicClock::default(),
+///     ))
+///     .id();
+///
+/// app.add_systems(Update, pulse.run_if(on_beat(music)));
+///
+/// fn pulse(clocks: Query<&MusicClock>) {
+///     for clock in &clocks {
+///         info!("Bar {}, beat {}", clock.bar(), clock.beat());
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default)]
+pub struct MusicClock {
+    /// Additional latency to compensate for, e.g. of the audio device or wireless headphones.
+    ///
+    /// Usually calibrated by the player.
+    pub latency_offset: Duration,
+    current: Option<Beat>,
+    pending: VecDeque<Beat>,
+    just_beat: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Beat {
+    bar: i32,
+    beat: i32,
+    position: i32,
+    tempo: f32,
+    time_signature: (i32, i32),
+    audible_at: Instant,
+}
+
+impl MusicClock {
+    /// Sets the additional latency to compensate for.
+    #[must_use]
+    pub fn with_latency_offset(mut self, latency_offset: Duration) -> Self {
+        self.latency_offset = latency_offset;
+        self
+    }
+
+    /// The current tempo in beats per minute, or `0.0` before the first beat.
+    pub fn tempo(&self) -> f32 {
+        self.current.map_or(0.0, |beat| beat.tempo)
+    }
+
+    /// The current bar, starting at 1, or `0` before the first beat.
+    pub fn bar(&self) -> i32 {
+        self.current.map_or(0, |beat| beat.bar)
+    }
+
+    /// The current beat within the bar, starting at 1, or `0` before the first beat.
+    pub fn beat(&self) -> i32 {
+        self.current.map_or(0, |beat| beat.beat)
+    }
+
+    /// The upper and lower number of the current time signature.
+    pub fn time_signature(&self) -> Option<(i32, i32)> {
+        self.current.map(|beat| beat.time_signature)
+    }
+
+    /// The timeline position of the current beat, in milliseconds.
+    pub fn timeline_position(&self) -> Option<i32> {
+        self.current.map(|beat| beat.position)
+    }
+
+    /// The duration of a beat at the current tempo.
+    pub fn beat_duration(&self) -> Option<Duration> {
+        self.current
+            .filter(|beat| beat.tempo > 0.0)
+            .map(|beat| Duration::from_secs_f32(60.0 / beat.tempo))
+    }
+
+    /// When the current beat became audible.
+    pub fn last_beat(&self) -> Option<Instant> {
+        self.current.map(|beat| beat.audible_at)
+    }
+
+    /// When the next beat is predicted to become audible, assuming the tempo does not change.
+    pub fn next_beat(&self) -> Option<Instant> {
+        Some(self.last_beat()? + self.beat_duration()?)
+    }
+
+    /// How far the music is between the current and the next beat at the given time, from `0.0`
+    /// to `1.0`. Useful for animating to the music.
+    pub fn beat_progress(&self, now: Instant) -> f32 {
+        let (Some(last_beat), Some(beat_duration)) = (self.last_beat(), self.beat_duration())
+        else {
+            return 0.0;
+        };
+
+        (now.saturating_duration_since(last_beat).as_secs_f32() / beat_duration.as_secs_f32())
+            .min(1.0)
+    }
+
+    /// Returns `true` if a beat became audible this frame.
+    pub fn just_beat(&self) -> bool {
+        self.just_beat
+    }
+
+    pub(crate) fn update(
+        studio: Res<FmodStudio>,
+        time: Res<Time<Real>>,
+        mut callbacks: MessageReader<FmodCallback>,
+        mut clocks: Query<&mut MusicClock>,
+        mut output_latency: Local<Option<Duration>>,
+    ) -> Result {
+        let output_latency = match *output_latency {
+            Some(output_latency) => output_latency,
+            None => *output_latency.insert(Self::output_latency(&studio)?),
+        };
+
+        for callback in callbacks.read() {
+            let Ok(mut clock) = clocks.get_mut(callback.entity) else {
+                continue;
+            };
+
+            match callback.kind {
+                FmodCallbackKind::TimelineBeat {
+                    bar,
+                    beat,
+                    position,
+                    tempo,
+                    time_signature,
+                } => {
+                    let audible_at = callback.received_at + output_latency + clock.latency_offset;
+
+                    clock.pending.push_back(Beat {
+                        bar,
+                        beat,
+                        position,
+                        tempo,
+                        time_signature,
+                        audible_at,
+                    });
+                }
+                // Beats which were not heard yet won't be heard at all.
+                FmodCallbackKind::Stopped => clock.pending.clear(),
+                _ => {}
+            }
+        }
+
+        let now = time.last_update().unwrap_or_else(Instant::now);
+
+        for mut clock in clocks.iter_mut() {
+            clock.advance(now);
+        }
+
+        Ok(())
+    }
+
+    /// Makes the pending beats that became audible by `now` current.
+    fn advance(&mut self, now: Instant) {
+        self.just_beat = false;
+
+        while self
+            .pending
+            .front()
+            .is_some_and(|beat| beat.audible_at <= now)
+        {
+            self.current = self.pending.pop_front();
+            self.just_beat = true;
+        }
+    }
+
+    /// The time it takes for mixed audio to become audible.
+    fn output_latency(studio: &FmodStudio) -> crate::Result<Duration> {
+        let core_system = studio.get_core_system()?;
+        let (buffer_length, num_buffers) = core_system.get_dsp_buffer_size()?;
+        let (sample_rate, _, _) = core_system.get_software_format()?;
+
+        Ok(buffer_latency(buffer_length, num_buffers, sample_rate))
+    }
+}
+
+/// The time it takes to play the given number of DSP buffers.
+///
+/// Returns [`Duration::ZERO`] for an unknown sample rate, instead of compensating for a latency
+/// that can't be computed.
+fn buffer_latency(buffer_length: u32, num_buffers: i32, sample_rate: i32) -> Duration {
+    if sample_rate <= 0 {
+        return Duration::ZERO;
+    }
+
+    let samples = f64::from(buffer_length) * f64::from(num_buffers.max(0));
+
+    Duration::from_secs_f64(samples / f64::from(sample_rate))
+}
+
+/// Run condition that returns `true` in frames in which a beat of the [`MusicClock`] on the given
+/// entity became audible.
+pub fn on_beat(entity: Entity) -> impl FnMut(Query<&MusicClock>) -> bool + Clone {
+    move |clocks: Query<&MusicClock>| clocks.get(entity).is_ok_and(MusicClock::just_beat)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::{RunSystemError, RunSystemOnce};
+    use bevy::prelude::World;
+
+    use super::*;
+
+    fn beat(bar: i32, beat: i32, audible_at: Instant) -> Beat {
+        Beat {
+            bar,
+            beat,
+            position: 0,
+            tempo: 120.0,
+            time_signature: (4, 4),
+            audible_at,
+        }
+    }
+
+    #[test]
+    fn latency_covers_all_dsp_buffers() {
+        assert_eq!(
+            buffer_latency(1024, 4, 48_000),
+            Duration::from_secs_f64(4096.0 / 48_000.0)
+        );
+        assert_eq!(
+            buffer_latency(512, 2, 44_100),
+            Duration::from_secs_f64(1024.0 / 44_100.0)
+        );
+        // An uninitialized software format must not divide by zero.
+        assert_eq!(buffer_latency(1024, 4, 0), Duration::ZERO);
+        assert_eq!(buffer_latency(1024, 4, -1), Duration::ZERO);
+        assert_eq!(buffer_latency(1024, -1, 48_000), Duration::ZERO);
+    }
+
+    #[test]
+    fn beats_become_current_once_audible() {
+        let start = Instant::now();
+        let mut clock = MusicClock::default();
+        clock
+            .pending
+            .push_back(beat(1, 1, start + Duration::from_millis(100)));
+        clock
+            .pending
+            .push_back(beat(1, 2, start + Duration::from_millis(600)));
+
+        clock.advance(start);
+        assert!(!clock.just_beat());
+        assert_eq!((clock.bar(), clock.beat()), (0, 0));
+
+        clock.advance(start + Duration::from_millis(100));
+        assert!(clock.just_beat());
+        assert_eq!((clock.bar(), clock.beat()), (1, 1));
+
+        clock.advance(start + Duration::from_millis(200));
+        assert!(!clock.just_beat());
+        assert_eq!(clock.beat(), 1);
+    }
+
+    #[test]
+    fn late_frames_skip_to_the_latest_beat() {
+        let start = Instant::now();
+        let mut clock = MusicClock::default();
+        clock.pending.push_back(beat(1, 1, start));
+        clock
+            .pending
+            .push_back(beat(1, 2, start + Duration::from_millis(500)));
+        clock
+            .pending
+            .push_back(beat(1, 3, start + Duration::from_millis(1000)));
+
+        clock.advance(start + Duration::from_millis(700));
+
+        assert!(clock.just_beat());
+        assert_eq!(clock.beat(), 2);
+        assert_eq!(clock.pending.len(), 1);
+    }
+
+    #[test]
+    fn predicts_the_next_beat_from_the_tempo() {
+        let start = Instant::now();
+        let mut clock = MusicClock::default();
+        assert_eq!(clock.beat_progress(start), 0.0);
+        assert_eq!(clock.next_beat(), None);
+
+        clock.pending.push_back(beat(1, 1, start));
+        clock.advance(start);
+
+        assert_eq!(clock.beat_duration(), Some(Duration::from_millis(500)));
+        assert_eq!(clock.next_beat(), Some(start + Duration::from_millis(500)));
+        assert_eq!(clock.beat_progress(start + Duration::from_millis(250)), 0.5);
+        assert_eq!(clock.beat_progress(start + Duration::from_secs(2)), 1.0);
+    }
+
+    #[test]
+    fn on_beat_follows_the_clock_of_the_entity() -> std::result::Result<(), RunSystemError> {
+        let start = Instant::now();
+        let mut world = World::new();
+        let mut clock = MusicClock::default();
+        clock.pending.push_back(beat(1, 1, start));
+        clock.advance(start);
+
+        let music = world.spawn(clock).id();
+        let silent = world.spawn(MusicClock::default()).id();
+        let other = world.spawn_empty().id();
+
+        assert!(world.run_system_once(on_beat(music))?);
+        assert!(!world.run_system_once(on_beat(silent))?);
+        assert!(!world.run_system_once(on_beat(other))?);
+
+        Ok(())
+    }
+}
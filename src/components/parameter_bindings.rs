@@ -0,0 +1,314 @@
+use std::any::TypeId;
+use std::ops::RangeInclusive;
+
+use bevy::log::warn;
+use bevy::math::{StableInterpolate, Vec2, Vec3};
+use bevy::prelude::{AppTypeRegistry, Component, Entity, ReflectComponent, Time, World};
+use bevy::reflect::{GetPath, PartialReflect, Reflect, TypeRegistry};
+
+use crate::components::fmod_parameters::{FmodParameters, ParameterKey};
+
+/// Drives local parameters from fields of other components, read through reflection.
+///
+/// Each [`ParameterBinding`] reads a value from a component on the same or another entity and
+/// writes it to the [`FmodParameters`] of this entity every frame. This replaces glue systems
+/// that copy gameplay values such as speed or health into FMOD.
+///
+/// The component must be registered for reflection with `#[reflect(Component)]`. Fields of type
+/// `f32`, `f64`, integers and `bool` are used as they are; `Vec2` and `Vec3` fields are used by
+/// their length.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::prelude::*;
+/// fn spawn_car(mut commands: Commands) {
+///     commands.spawn((
+///         FmodEvent::new("event:/Vehicles/Car Engine").with_autoplay(true),
+///         ParameterBindings::new().with(
+///             ParameterBinding::new::<Velocity>("current_velocity", "RPM")
+///                 .with_remap(0.0..=30.0, 0.0..=8000.0)
+///                 .with_smoothing(10.0),
+///         ),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default)]
+#[require(FmodParameters)]
+pub struct ParameterBindings {
+    bindings: Vec<ParameterBinding>,
+}
+
+/// Binds a local parameter to a field of a component. See [`ParameterBindings`].
+#[derive(Debug, Clone)]
+pub struct ParameterBinding {
+    parameter: ParameterKey,
+    component: TypeId,
+    component_name: &'static str,
+    path: String,
+    source: Option<Entity>,
+    remap: Option<(RangeInclusive<f32>, RangeInclusive<f32>)>,
+    smoothing: Option<f32>,
+    value: Option<f32>,
+    warned: bool,
+}
+
+impl ParameterBinding {
+    /// Binds the parameter to the field at the given reflection path of the component `C`, e.g.
+    /// `"translation.y"` of a [`Transform`](bevy::prelude::Transform).
+    pub fn new<C: Component + Reflect>(
+        path: impl Into<String>,
+        parameter: impl Into<ParameterKey>,
+    ) -> Self {
+        ParameterBinding {
+            parameter: parameter.into(),
+            component: TypeId::of::<C>(),
+            component_name: std::any::type_name::<C>(),
+            path: path.into(),
+            source: None,
+            remap: None,
+            smoothing: None,
+            value: None,
+            warned: false,
+        }
+    }
+
+    /// Reads the component from another entity instead of the one with the bindings.
+    #[must_use]
+    pub fn with_source(mut self, entity: Entity) -> Self {
+        self.source = Some(entity);
+        self
+    }
+
+    /// Maps values from the `input` range to the `output` range. Values outside the input range
+    /// are clamped. If the input range is a single value, values below it map to the start of the
+    /// output range and all others to its end.
+    #[must_use]
+    pub fn with_remap(mut self, input: RangeInclusive<f32>, output: RangeInclusive<f32>) -> Self {
+        self.remap = Some((input, output));
+        self
+    }
+
+    /// Smooths changes of the value exponentially. Higher decay rates follow the value faster;
+    /// a decay rate of `10.0` covers about two thirds of a change in a tenth of a second.
+    #[must_use]
+    pub fn with_smoothing(mut self, decay_rate: f32) -> Self {
+        self.smoothing = Some(decay_rate);
+        self
+    }
+
+    /// Reads the bound field.
+    fn read(&self, world: &World, entity: Entity, registry: &TypeRegistry) -> BoundValue {
+        let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(self.component)
+        else {
+            return Err(format!("{} is not reflected", self.component_name));
+        };
+
+        let Ok(entity) = world.get_entity(self.source.unwrap_or(entity)) else {
+            return Ok(None);
+        };
+
+        let Some(component) = reflect_component.reflect(entity) else {
+            return Ok(None);
+        };
+
+        let field = component
+            .reflect_path(self.path.as_str())
+            .map_err(|e| e.to_string())?;
+
+        match as_f32(field) {
+            Some(value) => Ok(Some(value)),
+            None => Err(format!("{} is not a number", self.path)),
+        }
+    }
+
+    /// Remaps and smooths the read value. Without a frame time, the value is not smoothed.
+    fn update(&mut self, raw: f32, delta: Option<f32>) -> f32 {
+        let mut target = raw;
+
+        if let Some((input, output)) = &self.remap {
+            let width = input.end() - input.start();
+
+            // An empty input range would divide by zero and send NaN to FMOD.
+            let progress = if width == 0.0 {
+                if raw < *input.start() { 0.0 } else { 1.0 }
+            } else {
+                ((raw - input.start()) / width).clamp(0.0, 1.0)
+            };
+
+            target = output.start() + (output.end() - output.start()) * progress;
+        }
+
+        let value = match (self.value, self.smoothing, delta) {
+            (Some(mut value), Some(decay_rate), Some(delta)) => {
+                value.smooth_nudge(&target, decay_rate, delta);
+                value
+            }
+            _ => target,
+        };
+
+        self.value = Some(value);
+        value
+    }
+}
+
+/// The value read by a [`ParameterBinding`]: `Ok(None)` if the component is missing and an error
+/// if the binding can't work at all.
+type BoundValue = Result<Option<f32>, String>;
+
+/// Converts a reflected number or vector to an `f32`.
+fn as_f32(field: &dyn PartialReflect) -> Option<f32> {
+    let field = field.try_as_reflect()?.as_any();
+
+    if let Some(value) = field.downcast_ref::<f32>() {
+        Some(*value)
+    } else if let Some(value) = field.downcast_ref::<f64>() {
+        Some(*value as f32)
+    } else if let Some(value) = field.downcast_ref::<i32>() {
+        Some(*value as f32)
+    } else if let Some(value) = field.downcast_ref::<u32>() {
+        Some(*value as f32)
+    } else if let Some(value) = field.downcast_ref::<i64>() {
+        Some(*value as f32)
+    } else if let Some(value) = field.downcast_ref::<u64>() {
+        Some(*value as f32)
+    } else if let Some(value) = field.downcast_ref::<usize>() {
+        Some(*value as f32)
+    } else if let Some(value) = field.downcast_ref::<bool>() {
+        Some(if *value { 1.0 } else { 0.0 })
+    } else if let Some(value) = field.downcast_ref::<Vec2>() {
+        Some(value.length())
+    } else {
+        field.downcast_ref::<Vec3>().map(|value| value.length())
+    }
+}
+
+impl ParameterBindings {
+    /// Creates an empty set of bindings.
+    pub fn new() -> Self {
+        ParameterBindings::default()
+    }
+
+    /// Adds a binding.
+    #[must_use]
+    pub fn with(mut self, binding: ParameterBinding) -> Self {
+        self.push(binding);
+        self
+    }
+
+    /// Adds a binding.
+    pub fn push(&mut self, binding: ParameterBinding) {
+        self.bindings.push(binding);
+    }
+
+    /// Removes all bindings of the given parameter. The parameter keeps its current value.
+    pub fn remove(&mut self, parameter: impl Into<ParameterKey>) {
+        let parameter = parameter.into();
+        self.bindings
+            .retain(|binding| binding.parameter != parameter);
+    }
+
+    pub(crate) fn evaluate(world: &mut World) {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        // Smoothing is skipped in apps without the `TimePlugin`.
+        let delta = world.get_resource::<Time>().map(Time::delta_secs);
+
+        // Read all bound values first, as the sources may be anywhere in the world.
+        let mut query = world.query::<(Entity, &ParameterBindings)>();
+        let values: Vec<(Entity, Vec<BoundValue>)> = query
+            .iter(world)
+            .map(|(entity, bindings)| {
+                let values = bindings
+                    .bindings
+                    .iter()
+                    .map(|binding| binding.read(world, entity, &registry))
+                    .collect();
+
+                (entity, values)
+            })
+            .collect();
+
+        for (entity, values) in values {
+            let Ok(mut entity) = world.get_entity_mut(entity) else {
+                continue;
+            };
+
+            let Some(mut bindings) = entity.get_mut::<ParameterBindings>() else {
+                continue;
+            };
+
+            let mut updates = Vec::new();
+
+            for (binding, value) in bindings.bindings.iter_mut().zip(values) {
+                match value {
+                    Ok(Some(value)) => {
+                        updates.push((binding.parameter.clone(), binding.update(value, delta)));
+                    }
+                    Ok(None) => {}
+                    // Warn only once instead of every frame.
+                    Err(reason) if !binding.warned => {
+                        warn!(
+                            "Could not bind {}::{} to parameter {:?}: {reason}",
+                            binding.component_name, binding.path, binding.parameter
+                        );
+                        binding.warned = true;
+                    }
+                    Err(_) => {}
+                }
+            }
+
+            let Some(mut parameters) = entity.get_mut::<FmodParameters>() else {
+                continue;
+            };
+
+            for (parameter, value) in updates {
+                // Only touch the parameters if the value changed, so they are only sent then.
+                if parameters.get(parameter.clone()) != Some(value) {
+                    parameters.set(parameter, value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Transform;
+
+    use super::*;
+
+    fn binding() -> ParameterBinding {
+        ParameterBinding::new::<Transform>("translation.y", "Height")
+    }
+
+    #[test]
+    fn remaps_and_clamps_to_the_output_range() {
+        let mut binding = binding().with_remap(0.0..=10.0, 100.0..=200.0);
+
+        assert_eq!(binding.update(5.0, None), 150.0);
+        assert_eq!(binding.update(-5.0, None), 100.0);
+        assert_eq!(binding.update(20.0, None), 200.0);
+    }
+
+    #[test]
+    fn empty_input_range_does_not_produce_nan() {
+        let mut binding = binding().with_remap(1.0..=1.0, 0.0..=8.0);
+
+        assert_eq!(binding.update(0.0, None), 0.0);
+        assert_eq!(binding.update(1.0, None), 8.0);
+        assert_eq!(binding.update(2.0, None), 8.0);
+    }
+
+    #[test]
+    fn smoothing_follows_the_value_over_time() {
+        let mut binding = binding().with_smoothing(10.0);
+
+        assert_eq!(binding.update(0.0, Some(0.1)), 0.0);
+
+        let value = binding.update(1.0, Some(0.1));
+        assert!(value > 0.5 && value < 1.0, "{value}");
+
+        // Without a frame time the value jumps to its target.
+        assert_eq!(binding.update(2.0, None), 2.0);
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::math::curve::{Curve, EaseFunction};
+use bevy::prelude::{Commands, Component, Entity, EntityEvent, Query, Real, Res, ResMut, Time};
+
+use crate::components::audio_source::AudioSource;
+use crate::components::fmod_parameters::{FmodParameters, ParameterKey};
+use crate::global_parameters::GlobalParameters;
+
+/// Animates parameters over time.
+///
+/// Tweens of local parameters drive the [`FmodParameters`] of the same entity. Tweens of global
+/// parameters drive the [`GlobalParameters`] and can live on any entity. Once a tween finished,
+/// a [`ParameterTweenCompleted`] event is triggered on the entity.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use bevy::math::curve::EaseFunction;
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::prelude::*;
+/// fn start_combat(mut commands: Commands) {
+///     commands
+///         .spawn((
+///             FmodEvent::new("event:/Music/Level 02").with_autoplay(true),
+///             ParameterTweens::new().with(
+///                 ParameterTween::local("Intensity")
+///                     .from(0.0)
+///                     .to(1.0, Duration::from_secs(4), EaseFunction::QuadraticInOut)
+///                     .to(0.5, Duration::from_secs(2), EaseFunction::Linear),
+///             ),
+///         ))
+///         .observe(|completed: On<ParameterTweenCompleted>| {
+///             info!("Finished tweening {:?}", completed.target);
+///         });
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default)]
+#[require(FmodParameters)]
+pub struct ParameterTweens {
+    tweens: Vec<ParameterTween>,
+}
+
+/// The parameter animated by a [`ParameterTween`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TweenTarget {
+    /// A local parameter of the [`AudioSource`] on the same entity.
+    Local(ParameterKey),
+    /// A global parameter.
+    Global(String),
+}
+
+/// The clock a [`ParameterTween`] advances with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TweenTime {
+    /// Virtual time, which stops while the game is paused.
+    #[default]
+    Virtual,
+    /// Real time, which keeps running while the game is paused.
+    Real,
+}
+
+/// Animates a parameter through one or more segments. See [`ParameterTweens`].
+#[derive(Debug, Clone)]
+pub struct ParameterTween {
+    target: TweenTarget,
+    time: TweenTime,
+    start: Option<f32>,
+    segments: VecDeque<TweenSegment>,
+    elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TweenSegment {
+    end: f32,
+    duration: Duration,
+    ease: EaseFunction,
+}
+
+/// Triggered on the entity of a [`ParameterTweens`] once one of its tweens finished. Not
+/// triggered for cancelled tweens.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct ParameterTweenCompleted {
+    /// The entity the tween was running on.
+    pub entity: Entity,
+    /// The parameter that was animated.
+    pub target: TweenTarget,
+}
+
+impl ParameterTween {
+    /// Creates a tween of a local parameter of the [`AudioSource`] on the same entity.
+    pub fn local(key: impl Into<ParameterKey>) -> Self {
+        Self::new(TweenTarget::Local(key.into()))
+    }
+
+    /// Creates a tween of a global parameter.
+    pub fn global(name: impl Into<String>) -> Self {
+        Self::new(TweenTarget::Global(name.into()))
+    }
+
+    fn new(target: TweenTarget) -> Self {
+        ParameterTween {
+            target,
+            time: TweenTime::default(),
+            start: None,
+            segments: VecDeque::new(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Sets the value to start from. Defaults to the current value of the parameter.
+    #[must_use]
+    pub fn from(mut self, start: f32) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Appends a segment that animates the parameter to `end` over `duration`.
+    #[must_use]
+    pub fn to(mut self, end: f32, duration: Duration, ease: EaseFunction) -> Self {
+        self.segments.push_back(TweenSegment {
+            end,
+            duration,
+            ease,
+        });
+        self
+    }
+
+    /// Sets the clock the tween advances with. Defaults to [`TweenTime::Virtual`].
+    #[must_use]
+    pub fn with_time(mut self, time: TweenTime) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// The parameter animated by this tween.
+    pub fn target(&self) -> &TweenTarget {
+        &self.target
+    }
+
+    /// Advances the tween and returns the new value of the parameter, or `None` once all segments
+    /// finished.
+    fn advance(&mut self, delta: Duration, start: f32) -> Option<f32> {
+        self.elapsed += delta;
+
+        let mut start = *self.start.get_or_insert(start);
+
+        while let Some(segment) = self.segments.front().copied() {
+            if self.elapsed < segment.duration {
+                let progress = self.elapsed.as_secs_f32() / segment.duration.as_secs_f32();
+                let eased = segment.ease.sample_clamped(progress);
+
+                return Some(start + (segment.end - start) * eased);
+            }
+
+            // Carry the remaining time over to the next segment.
+            self.elapsed -= segment.duration;
+            self.segments.pop_front();
+            start = segment.end;
+            self.start = Some(start);
+
+            if self.segments.is_empty() {
+                return Some(segment.end);
+            }
+        }
+
+        None
+    }
+}
+
+impl ParameterTweens {
+    /// Creates an empty set of tweens.
+    pub fn new() -> Self {
+        ParameterTweens::default()
+    }
+
+    /// Adds a tween. See [`push`](Self::push).
+    #[must_use]
+    pub fn with(mut self, tween: ParameterTween) -> Self {
+        self.push(tween);
+        self
+    }
+
+    /// Starts a tween, replacing any tween of the same parameter.
+    pub fn push(&mut self, tween: ParameterTween) {
+        self.cancel(&tween.target);
+        self.tweens.push(tween);
+    }
+
+    /// Stops the tween of the given parameter, leaving it at its current value.
+    pub fn cancel(&mut self, target: &TweenTarget) {
+        self.tweens.retain(|tween| tween.target != *target);
+    }
+
+    /// Stops all tweens.
+    pub fn clear(&mut self) {
+        self.tweens.clear();
+    }
+
+    /// Returns `true` if the given parameter is being tweened.
+    pub fn is_tweening(&self, target: &TweenTarget) -> bool {
+        self.tweens.iter().any(|tween| tween.target == *target)
+    }
+
+    pub(crate) fn advance(
+        mut commands: Commands,
+        virtual_time: Res<Time>,
+        real_time: Res<Time<Real>>,
+        mut global_parameters: ResMut<GlobalParameters>,
+        mut query: Query<(
+            Entity,
+            &mut ParameterTweens,
+            &mut FmodParameters,
+            Option<&AudioSource>,
+        )>,
+    ) {
+        for (entity, mut tweens, mut parameters, audio_source) in query.iter_mut() {
+            if tweens.tweens.is_empty() {
+                continue;
+            }
+
+            let mut completed = Vec::new();
+
+            tweens.tweens.retain_mut(|tween| {
+                let delta = match tween.time {
+                    TweenTime::Virtual => virtual_time.delta(),
+                    TweenTime::Real => real_time.delta(),
+                };
+
+                let value = match &tween.target {
+                    TweenTarget::Local(key) => {
+                        let current = parameters
+                            .get(key.clone())
+                            .or_else(|| current_local_value(audio_source?, key));
+
+                        tween.advance(delta, current.unwrap_or_default())
+                    }
+                    TweenTarget::Global(name) => {
+                        let current = global_parameters.value(name);
+
+                        tween.advance(delta, current.unwrap_or_default())
+                    }
+                };
+
+                if let Some(value) = value {
+                    match &tween.target {
+                        TweenTarget::Local(key) => parameters.set(key.clone(), value),
+                        TweenTarget::Global(name) => global_parameters.set(name.clone(), value),
+                    }
+                }
+
+                let finished = tween.segments.is_empty();
+
+                if finished {
+                    completed.push(tween.target.clone());
+                }
+
+                !finished
+            });
+
+            for target in completed {
+                commands.trigger(ParameterTweenCompleted { entity, target });
+            }
+        }
+    }
+}
+
+/// The value of a local parameter as reported by FMOD.
+fn current_local_value(audio_source: &AudioSource, key: &ParameterKey) -> Option<f32> {
+    if !audio_source.is_valid() {
+        return None;
+    }
+
+    let value = match key {
+        ParameterKey::Name(name) => audio_source.get_parameter_by_name(name),
+        ParameterKey::Id(id) => audio_source.get_parameter_by_id(id.clone()),
+    };
+
+    value.ok().map(|(value, _)| value)
+}
@@ -0,0 +1,107 @@
+use bevy::ecs::error::Result;
+use bevy::prelude::{Component, DetectChangesMut, Query};
+use libfmod::PlaybackState;
+
+use crate::components::audio_source::AudioSource;
+
+/// The playback status of the event instance of an [`AudioSource`], inserted automatically
+/// alongside it.
+///
+/// Updated every frame in `PreUpdate`, and only marked as changed when the status actually
+/// changed. This allows using `Changed<FmodPlaybackStatus>` instead of querying FMOD. Note that
+/// the timeline position changes every frame while an event is playing.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::prelude::*;
+/// fn log_stopped(query: Query<(Entity, &FmodPlaybackStatus), Changed<FmodPlaybackStatus>>) {
+///     for (entity, status) in &query {
+///         if status.is_stopped() {
+///             info!("{entity} stopped playing");
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct FmodPlaybackStatus {
+    state: PlaybackState,
+    paused: bool,
+    is_virtual: bool,
+    timeline_position: i32,
+    length: i32,
+}
+
+impl Default for FmodPlaybackStatus {
+    fn default() -> Self {
+        FmodPlaybackStatus {
+            state: PlaybackState::Stopped,
+            paused: false,
+            is_virtual: false,
+            timeline_position: 0,
+            length: 0,
+        }
+    }
+}
+
+impl FmodPlaybackStatus {
+    /// The playback state of the event instance.
+    pub fn state(&self) -> PlaybackState {
+        self.state
+    }
+
+    /// Returns `true` if the event instance is playing, including while it is starting,
+    /// sustaining or stopping.
+    pub fn is_playing(&self) -> bool {
+        self.state != PlaybackState::Stopped
+    }
+
+    /// Returns `true` if the event instance is stopped.
+    pub fn is_stopped(&self) -> bool {
+        self.state == PlaybackState::Stopped
+    }
+
+    /// Returns `true` if the event instance is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns `true` if the event instance was virtualized and is currently inaudible.
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+
+    /// The timeline position of the event instance, in milliseconds.
+    pub fn timeline_position(&self) -> i32 {
+        self.timeline_position
+    }
+
+    /// The length of the event's timeline in milliseconds, or `0` for events without a timeline.
+    pub fn length(&self) -> i32 {
+        self.length
+    }
+
+    pub(crate) fn update(mut query: Query<(&AudioSource, &mut FmodPlaybackStatus)>) -> Result {
+        for (audio_source, mut status) in query.iter_mut() {
+            let new_status = if audio_source.is_valid() {
+                Self::read(audio_source)?
+            } else {
+                // The instance was released together with its bank.
+                FmodPlaybackStatus::default()
+            };
+
+            status.set_if_neq(new_status);
+        }
+
+        Ok(())
+    }
+
+    fn read(audio_source: &AudioSource) -> crate::Result<Self> {
+        Ok(FmodPlaybackStatus {
+            state: audio_source.get_playback_state()?,
+            paused: audio_source.get_paused()?,
+            is_virtual: audio_source.is_virtual()?,
+            timeline_position: audio_source.get_timeline_position()?,
+            length: audio_source.get_description()?.get_length()?,
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::app::{App, Plugin, Update};
 use bevy::math::Vec3;
-use bevy::prelude::{Component, GlobalTransform, Local, Query, Res, Time};
+use bevy::prelude::{Component, GlobalTransform, Local, Query, ReflectComponent, Res, Time};
+use bevy::reflect::Reflect;
 
 /// Automatic velocity updates for
 /// [`AudioListener`](crate::components::audio_listener::AudioListener) and
@@ -10,7 +11,8 @@ use bevy::prelude::{Component, GlobalTransform, Local, Query, Res, Time};
 /// to enable the Doppler effect. The recommended way to do this is to use the
 /// [`SpatialAudioBundle`](crate::components::bundles::SpatialAudioBundle) and
 /// [`SpatialListenerBundle`](crate::components::bundles::SpatialListenerBundle).
-#[derive(Component, Default)]
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
 pub struct Velocity {
     last_position: Vec3,
     pub(crate) current_velocity: Vec3,
@@ -43,6 +45,7 @@ impl VelocityPlugin {
 
 impl Plugin for VelocityPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, Self::update_velocity);
+        app.register_type::<Velocity>()
+            .add_systems(Update, Self::update_velocity);
     }
 }
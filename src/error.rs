@@ -11,4 +11,16 @@ pub enum Error {
     /// IO errors
     #[error(transparent)]
     Io(#[from] std::io::Error),
+    /// Strings passed to FMOD must not contain null bytes
+    #[error(transparent)]
+    Nul(#[from] std::ffi::NulError),
+    /// Paths passed to FMOD must be valid unicode
+    #[error("Path is not valid unicode: {0:?}")]
+    NonUnicodePath(std::path::PathBuf),
+    /// A WAV file could not be read or compared
+    #[error("Invalid WAV file: {0}")]
+    InvalidWav(&'static str),
+    /// The sample data of a bank failed to load
+    #[error("The sample data of the bank failed to load")]
+    SampleDataLoadFailed,
 }
@@ -0,0 +1,302 @@
+//! FMOD event callbacks, delivered to Bevy.
+//!
+//! Every [`AudioSource`] gets a callback registered on its event instance when it is inserted.
+//! FMOD invokes it from its own thread, so the callbacks are queued and delivered in `PreUpdate`,
+//! both as a [`FmodCallback`] message and as an entity event triggered on the entity owning the
+//! audio source:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_fmod::prelude::*;
+//! fn spawn_music(mut commands: Commands) {
+//!     commands
+//!         .spawn(FmodEvent::new("event:/Music/Level 01").with_autoplay(true))
+//!         .observe(|callback: On<FmodCallback>| {
+//!             if let FmodCallbackKind::TimelineMarker { name, .. } = &callback.kind {
+//!                 info!("Reached marker {name}");
+//!             }
+//!         });
+//! }
+//!
+//! fn log_beats(mut callbacks: MessageReader<FmodCallback>) {
+//!     for callback in callbacks.read() {
+//!         if let FmodCallbackKind::TimelineBeat { bar, beat, .. } = callback.kind {
+//!             info!("{:?}: bar {bar}, beat {beat}", callback.entity);
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Registering the callback replaces any callback and user data previously set on the event
+//! instance. When the event instance of an audio source is replaced, e.g. after its bank was
+//! reloaded, the callback is registered on the new instance before callbacks are delivered.
+
+use std::collections::HashMap;
+use std::ffi::{CStr, c_void};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bevy::ecs::error::Result;
+use bevy::ecs::lifecycle::Insert;
+use bevy::ecs::observer::On;
+use bevy::platform::time::Instant;
+use bevy::prelude::{
+    Changed, Commands, Entity, EntityEvent, Message, MessageWriter, Query, Res, Resource,
+};
+use libfmod::EventInstance;
+use libfmod::ffi::{
+    FMOD_OK, FMOD_RESULT, FMOD_STUDIO_EVENT_CALLBACK_DESTROYED,
+    FMOD_STUDIO_EVENT_CALLBACK_REAL_TO_VIRTUAL, FMOD_STUDIO_EVENT_CALLBACK_RESTARTED,
+    FMOD_STUDIO_EVENT_CALLBACK_SOUND_PLAYED, FMOD_STUDIO_EVENT_CALLBACK_SOUND_STOPPED,
+    FMOD_STUDIO_EVENT_CALLBACK_START_FAILED, FMOD_STUDIO_EVENT_CALLBACK_STARTED,
+    FMOD_STUDIO_EVENT_CALLBACK_STOPPED, FMOD_STUDIO_EVENT_CALLBACK_TIMELINE_BEAT,
+    FMOD_STUDIO_EVENT_CALLBACK_TIMELINE_MARKER, FMOD_STUDIO_EVENT_CALLBACK_TYPE,
+    FMOD_STUDIO_EVENT_CALLBACK_VIRTUAL_TO_REAL, FMOD_STUDIO_EVENTINSTANCE,
+    FMOD_STUDIO_TIMELINE_BEAT_PROPERTIES, FMOD_STUDIO_TIMELINE_MARKER_PROPERTIES,
+};
+
+use crate::components::audio_source::AudioSource;
+
+/// The callback types forwarded to Bevy.
+const CALLBACK_MASK: FMOD_STUDIO_EVENT_CALLBACK_TYPE = FMOD_STUDIO_EVENT_CALLBACK_STARTED
+    | FMOD_STUDIO_EVENT_CALLBACK_RESTARTED
+    | FMOD_STUDIO_EVENT_CALLBACK_STOPPED
+    | FMOD_STUDIO_EVENT_CALLBACK_START_FAILED
+    | FMOD_STUDIO_EVENT_CALLBACK_TIMELINE_MARKER
+    | FMOD_STUDIO_EVENT_CALLBACK_TIMELINE_BEAT
+    | FMOD_STUDIO_EVENT_CALLBACK_SOUND_PLAYED
+    | FMOD_STUDIO_EVENT_CALLBACK_SOUND_STOPPED
+    | FMOD_STUDIO_EVENT_CALLBACK_REAL_TO_VIRTUAL
+    | FMOD_STUDIO_EVENT_CALLBACK_VIRTUAL_TO_REAL
+    | FMOD_STUDIO_EVENT_CALLBACK_DESTROYED;
+
+/// Callbacks received on the FMOD thread which were not delivered yet, by the address of the
+/// event instance they belong to.
+type PendingCallbacks = Mutex<Vec<(usize, FmodCallbackKind, Instant)>>;
+
+/// The callbacks received for the event instances of one app.
+///
+/// Every event instance with a registered callback holds a reference to the queue in its user
+/// data, which is dropped when FMOD destroys the instance. This keeps the queue alive for as long
+/// as FMOD may invoke the callback, and apps running side by side, e.g. in parallel tests, don't
+/// receive each other's callbacks.
+#[derive(Resource, Default)]
+pub(crate) struct CallbackQueue(Arc<PendingCallbacks>);
+
+/// An FMOD event callback of the event instance of an [`AudioSource`].
+///
+/// Written as a message and triggered as an entity event on the entity owning the audio source.
+#[derive(Message, EntityEvent, Debug, Clone)]
+pub struct FmodCallback {
+    /// The entity owning the [`AudioSource`].
+    pub entity: Entity,
+    /// What happened.
+    pub kind: FmodCallbackKind,
+    /// When FMOD invoked the callback, which may be up to a frame before it is delivered.
+    pub received_at: Instant,
+}
+
+/// The type of an [`FmodCallback`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FmodCallbackKind {
+    /// The event started playing.
+    Started,
+    /// The event restarted while it was already playing.
+    Restarted,
+    /// The event stopped playing.
+    Stopped,
+    /// The event failed to start, e.g. because of polyphony limits.
+    StartFailed,
+    /// The timeline passed a named marker.
+    TimelineMarker {
+        /// The name of the marker.
+        name: String,
+        /// The position of the marker on the timeline, in milliseconds.
+        position: i32,
+    },
+    /// The timeline hit a beat of a tempo marker.
+    TimelineBeat {
+        /// The bar number, starting at 1.
+        bar: i32,
+        /// The beat number within the bar, starting at 1.
+        beat: i32,
+        /// The position of the beat on the timeline, in milliseconds.
+        position: i32,
+        /// The tempo in beats per minute.
+        tempo: f32,
+        /// The upper and lower number of the time signature.
+        time_signature: (i32, i32),
+    },
+    /// An instrument of the event started playing a sound.
+    SoundPlayed,
+    /// An instrument of the event stopped playing a sound.
+    SoundStopped,
+    /// The event became virtual and is no longer audible.
+    RealToVirtual,
+    /// The event became real and is audible again.
+    VirtualToReal,
+}
+
+impl FmodCallbackKind {
+    /// Reads the callback received from FMOD.
+    ///
+    /// # Safety
+    ///
+    /// `parameters` must point to the parameters FMOD passes for the given callback type.
+    unsafe fn from_raw(
+        callback_type: FMOD_STUDIO_EVENT_CALLBACK_TYPE,
+        parameters: *mut c_void,
+    ) -> Option<Self> {
+        let kind = match callback_type {
+            FMOD_STUDIO_EVENT_CALLBACK_STARTED => FmodCallbackKind::Started,
+            FMOD_STUDIO_EVENT_CALLBACK_RESTARTED => FmodCallbackKind::Restarted,
+            FMOD_STUDIO_EVENT_CALLBACK_STOPPED => FmodCallbackKind::Stopped,
+            FMOD_STUDIO_EVENT_CALLBACK_START_FAILED => FmodCallbackKind::StartFailed,
+            FMOD_STUDIO_EVENT_CALLBACK_TIMELINE_MARKER => {
+                // SAFETY: FMOD passes the marker properties for this callback type.
+                let properties =
+                    unsafe { &*parameters.cast::<FMOD_STUDIO_TIMELINE_MARKER_PROPERTIES>() };
+                // SAFETY: The marker name is a valid C string for the duration of the callback.
+                let name = unsafe { CStr::from_ptr(properties.name) };
+
+                FmodCallbackKind::TimelineMarker {
+                    name: name.to_string_lossy().into_owned(),
+                    position: properties.position,
+                }
+            }
+            FMOD_STUDIO_EVENT_CALLBACK_TIMELINE_BEAT => {
+                // SAFETY: FMOD passes the beat properties for this callback type.
+                let properties =
+                    unsafe { &*parameters.cast::<FMOD_STUDIO_TIMELINE_BEAT_PROPERTIES>() };
+
+                FmodCallbackKind::TimelineBeat {
+                    bar: properties.bar,
+                    beat: properties.beat,
+                    position: properties.position,
+                    tempo: properties.tempo,
+                    time_signature: (properties.timesignatureupper, properties.timesignaturelower),
+                }
+            }
+            FMOD_STUDIO_EVENT_CALLBACK_SOUND_PLAYED => FmodCallbackKind::SoundPlayed,
+            FMOD_STUDIO_EVENT_CALLBACK_SOUND_STOPPED => FmodCallbackKind::SoundStopped,
+            FMOD_STUDIO_EVENT_CALLBACK_REAL_TO_VIRTUAL => FmodCallbackKind::RealToVirtual,
+            FMOD_STUDIO_EVENT_CALLBACK_VIRTUAL_TO_REAL => FmodCallbackKind::VirtualToReal,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+}
+
+/// Called by FMOD, possibly from its own thread. Must not panic.
+unsafe extern "C" fn event_callback(
+    callback_type: FMOD_STUDIO_EVENT_CALLBACK_TYPE,
+    event: *mut FMOD_STUDIO_EVENTINSTANCE,
+    parameters: *mut c_void,
+) -> FMOD_RESULT {
+    let Ok(user_data) = EventInstance::from(event).get_user_data() else {
+        return FMOD_OK;
+    };
+
+    if user_data.is_null() {
+        return FMOD_OK;
+    }
+
+    let queue = user_data.cast_const().cast::<PendingCallbacks>();
+
+    if callback_type == FMOD_STUDIO_EVENT_CALLBACK_DESTROYED {
+        // SAFETY: The user data holds the reference created in `register_callback`, and this is
+        // the last callback of the instance.
+        drop(unsafe { Arc::from_raw(queue) });
+        return FMOD_OK;
+    }
+
+    // SAFETY: FMOD passes matching parameters for every callback type.
+    if let Some(kind) = unsafe { FmodCallbackKind::from_raw(callback_type, parameters) } {
+        // SAFETY: The reference held by the user data is only dropped once the instance is
+        // destroyed.
+        unsafe { &*queue }
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((event as usize, kind, Instant::now()));
+    }
+
+    FMOD_OK
+}
+
+/// Registers the callback forwarding to the given queue on the event instance, unless it is
+/// registered already.
+pub(crate) fn register_callback(
+    event_instance: EventInstance,
+    queue: &CallbackQueue,
+) -> crate::Result<()> {
+    if event_instance.get_user_data()? == Arc::as_ptr(&queue.0).cast_mut().cast() {
+        return Ok(());
+    }
+
+    event_instance.set_user_data(Arc::into_raw(queue.0.clone()).cast_mut().cast())?;
+    event_instance.set_callback(Some(event_callback), CALLBACK_MASK)?;
+
+    Ok(())
+}
+
+pub(crate) fn on_insert_audio_source(
+    insert: On<Insert, AudioSource>,
+    query: Query<&AudioSource>,
+    queue: Res<CallbackQueue>,
+) -> Result {
+    let audio_source = query.get(insert.entity)?;
+
+    if audio_source.is_valid() {
+        register_callback(audio_source.event_instance, &queue)?;
+    }
+
+    Ok(())
+}
+
+/// Registers the callback on event instances that replaced the instance of an audio source.
+pub(crate) fn register_replaced_instances(
+    query: Query<&AudioSource, Changed<AudioSource>>,
+    queue: Res<CallbackQueue>,
+) -> Result {
+    for audio_source in query.iter() {
+        if audio_source.is_valid() {
+            register_callback(audio_source.event_instance, &queue)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Delivers the callbacks received since the last run.
+pub(crate) fn deliver_callbacks(
+    mut commands: Commands,
+    mut messages: MessageWriter<FmodCallback>,
+    audio_sources: Query<(Entity, &AudioSource)>,
+    queue: Res<CallbackQueue>,
+) {
+    let pending = std::mem::take(&mut *queue.0.lock().unwrap_or_else(PoisonError::into_inner));
+
+    if pending.is_empty() {
+        return;
+    }
+
+    let entities: HashMap<usize, Entity> = audio_sources
+        .iter()
+        .map(|(entity, audio_source)| (audio_source.event_instance.as_mut_ptr() as usize, entity))
+        .collect();
+
+    for (event, kind, received_at) in pending {
+        // The audio source may have been removed since the callback was received.
+        let Some(&entity) = entities.get(&event) else {
+            continue;
+        };
+
+        let callback = FmodCallback {
+            entity,
+            kind,
+            received_at,
+        };
+        messages.write(callback.clone());
+        commands.trigger(callback);
+    }
+}
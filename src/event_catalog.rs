@@ -0,0 +1,475 @@
+//! Browsing the contents of the loaded banks.
+//!
+//! The [`FmodEventCatalog`] resource lists every loaded bank with its events, snapshots, buses
+//! and VCAs, as well as the global parameters. It is rebuilt whenever a bank finishes loading or
+//! is unloaded, so tools and debug UIs can use change detection to follow it. Banks that can't be
+//! described are left out with a warning:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_fmod::prelude::*;
+//! fn list_events(catalog: Res<FmodEventCatalog>) {
+//!     if !catalog.is_changed() {
+//!         return;
+//!     }
+//!
+//!     for event in catalog.events() {
+//!         info!("{} ({} ms, 3D: {})", event.path(), event.length(), event.is_3d());
+//!     }
+//! }
+//! ```
+
+use std::ffi::CStr;
+
+use bevy::ecs::error::Result;
+use bevy::log::warn;
+use bevy::prelude::{MessageReader, Res, ResMut, Resource};
+use libfmod::ffi::FMOD_STUDIO_PARAMETER_LABELED;
+use libfmod::{
+    Bank, EventDescription, Guid, LoadingState, ParameterDescription, UserProperty,
+    UserPropertyType,
+};
+
+use crate::bank_loading::BankLoaded;
+use crate::fmod_studio::FmodStudio;
+
+/// The contents of all loaded banks. See the [module documentation](self).
+#[derive(Resource, Debug, Default)]
+pub struct FmodEventCatalog {
+    banks: Vec<CatalogBank>,
+    parameters: Vec<ParameterDescription>,
+    /// The loaded banks the catalog was built from, including those that could not be described.
+    loaded: Vec<Bank>,
+}
+
+/// A loaded bank in the [`FmodEventCatalog`].
+#[derive(Debug, Clone)]
+pub struct CatalogBank {
+    path: String,
+    bank: Bank,
+    events: Vec<CatalogEvent>,
+    snapshots: Vec<CatalogEvent>,
+    buses: Vec<String>,
+    vcas: Vec<String>,
+}
+
+/// An event or snapshot in the [`FmodEventCatalog`].
+#[derive(Debug, Clone)]
+pub struct CatalogEvent {
+    path: String,
+    id: Guid,
+    description: EventDescription,
+    is_3d: bool,
+    is_oneshot: bool,
+    is_snapshot: bool,
+    is_stream: bool,
+    length: i32,
+    min_distance: f32,
+    max_distance: f32,
+    user_properties: Vec<(String, UserPropertyValue)>,
+    parameters: Vec<ParameterDescription>,
+}
+
+/// The value of a user property set on an event in FMOD Studio.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserPropertyValue {
+    /// An integer property.
+    Integer(i32),
+    /// A boolean property.
+    Boolean(bool),
+    /// A floating point property.
+    Float(f32),
+    /// A string property.
+    String(String),
+}
+
+impl CatalogBank {
+    /// The path of the bank, e.g. `bank:/Master`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The bank.
+    pub fn bank(&self) -> Bank {
+        self.bank
+    }
+
+    /// The events of the bank, sorted by path.
+    pub fn events(&self) -> &[CatalogEvent] {
+        &self.events
+    }
+
+    /// The snapshots of the bank, sorted by path.
+    pub fn snapshots(&self) -> &[CatalogEvent] {
+        &self.snapshots
+    }
+
+    /// The paths of the buses of the bank, sorted.
+    pub fn buses(&self) -> &[String] {
+        &self.buses
+    }
+
+    /// The paths of the VCAs of the bank, sorted.
+    pub fn vcas(&self) -> &[String] {
+        &self.vcas
+    }
+
+    fn describe(bank: Bank) -> crate::Result<Self> {
+        let mut events = Vec::new();
+        let mut snapshots = Vec::new();
+
+        for description in bank.get_event_list(bank.get_event_count()?)? {
+            let event = CatalogEvent::describe(description)?;
+
+            if event.is_snapshot {
+                snapshots.push(event);
+            } else {
+                events.push(event);
+            }
+        }
+
+        // Buses and VCAs have no path and are skipped without the strings bank.
+        let mut buses = bank
+            .get_bus_list(bank.get_bus_count()?)?
+            .into_iter()
+            .filter_map(|bus| bus.get_path().ok())
+            .collect::<Vec<_>>();
+
+        let mut vcas = bank
+            .get_vca_list(bank.get_vca_count()?)?
+            .into_iter()
+            .filter_map(|vca| vca.get_path().ok())
+            .collect::<Vec<_>>();
+
+        events.sort_by(|a, b| a.path.cmp(&b.path));
+        snapshots.sort_by(|a, b| a.path.cmp(&b.path));
+        buses.sort();
+        vcas.sort();
+
+        // Without the strings bank, banks and events are listed by their GUID.
+        let path = match bank.get_path() {
+            Ok(path) => path,
+            Err(_) => guid_string(&bank.get_id()?),
+        };
+
+        Ok(CatalogBank {
+            path,
+            bank,
+            events,
+            snapshots,
+            buses,
+            vcas,
+        })
+    }
+}
+
+impl CatalogEvent {
+    /// The path of the event, e.g. `event:/Music/Level 01`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The GUID of the event.
+    pub fn id(&self) -> Guid {
+        self.id.clone()
+    }
+
+    /// The description of the event, for creating instances.
+    pub fn description(&self) -> EventDescription {
+        self.description
+    }
+
+    /// Whether the event is positioned in 3D.
+    pub fn is_3d(&self) -> bool {
+        self.is_3d
+    }
+
+    /// Whether the event stops by itself, without a loop or sustain point.
+    pub fn is_oneshot(&self) -> bool {
+        self.is_oneshot
+    }
+
+    /// Whether this is a snapshot.
+    pub fn is_snapshot(&self) -> bool {
+        self.is_snapshot
+    }
+
+    /// Whether the event contains streamed sounds.
+    pub fn is_stream(&self) -> bool {
+        self.is_stream
+    }
+
+    /// The length of the event's timeline in milliseconds, or `0` for events without a timeline.
+    pub fn length(&self) -> i32 {
+        self.length
+    }
+
+    /// The distance below which the event is not attenuated.
+    pub fn min_distance(&self) -> f32 {
+        self.min_distance
+    }
+
+    /// The distance above which the event is fully attenuated.
+    pub fn max_distance(&self) -> f32 {
+        self.max_distance
+    }
+
+    /// The user properties of the event, by name.
+    pub fn user_properties(&self) -> &[(String, UserPropertyValue)] {
+        &self.user_properties
+    }
+
+    /// The value of a user property.
+    pub fn user_property(&self, name: &str) -> Option<&UserPropertyValue> {
+        self.user_properties
+            .iter()
+            .find(|(property, _)| property == name)
+            .map(|(_, value)| value)
+    }
+
+    /// The parameters of the event, including automatic and global parameters it uses.
+    pub fn parameters(&self) -> &[ParameterDescription] {
+        &self.parameters
+    }
+
+    fn describe(description: EventDescription) -> crate::Result<Self> {
+        let id = description.get_id()?;
+        let (min_distance, max_distance) = description.get_min_max_distance()?;
+
+        let user_properties = (0..description.get_user_property_count()?)
+            .map(|index| {
+                description
+                    .get_user_property_by_index(index)
+                    .map(user_property)
+            })
+            .collect::<std::result::Result<_, _>>()?;
+
+        let parameters = (0..description.get_parameter_description_count()?)
+            .map(|index| description.get_parameter_description_by_index(index))
+            .collect::<std::result::Result<_, _>>()?;
+
+        Ok(CatalogEvent {
+            path: description.get_path().unwrap_or_else(|_| guid_string(&id)),
+            id,
+            description,
+            is_3d: description.is_3d()?,
+            is_oneshot: description.is_oneshot()?,
+            is_snapshot: description.is_snapshot()?,
+            is_stream: description.is_stream()?,
+            length: description.get_length()?,
+            min_distance,
+            max_distance,
+            user_properties,
+            parameters,
+        })
+    }
+}
+
+impl FmodEventCatalog {
+    /// The loaded banks, in the order they were loaded.
+    pub fn banks(&self) -> &[CatalogBank] {
+        &self.banks
+    }
+
+    /// The events of all loaded banks.
+    pub fn events(&self) -> impl Iterator<Item = &CatalogEvent> {
+        self.banks.iter().flat_map(|bank| bank.events.iter())
+    }
+
+    /// The snapshots of all loaded banks.
+    pub fn snapshots(&self) -> impl Iterator<Item = &CatalogEvent> {
+        self.banks.iter().flat_map(|bank| bank.snapshots.iter())
+    }
+
+    /// The paths of the buses of all loaded banks.
+    pub fn buses(&self) -> impl Iterator<Item = &str> {
+        self.banks
+            .iter()
+            .flat_map(|bank| bank.buses.iter().map(String::as_str))
+    }
+
+    /// The paths of the VCAs of all loaded banks.
+    pub fn vcas(&self) -> impl Iterator<Item = &str> {
+        self.banks
+            .iter()
+            .flat_map(|bank| bank.vcas.iter().map(String::as_str))
+    }
+
+    /// The global parameters of all loaded banks.
+    pub fn parameters(&self) -> &[ParameterDescription] {
+        &self.parameters
+    }
+
+    /// Finds an event or snapshot by its path.
+    pub fn event(&self, path: &str) -> Option<&CatalogEvent> {
+        self.events()
+            .chain(self.snapshots())
+            .find(|event| event.path == path)
+    }
+
+    pub(crate) fn refresh(
+        studio: Res<FmodStudio>,
+        mut bank_loaded: MessageReader<BankLoaded>,
+        mut catalog: ResMut<FmodEventCatalog>,
+    ) -> Result {
+        let count = studio.get_bank_count()?;
+        let mut loaded = Vec::new();
+
+        // Banks that are still loading are added once they finished.
+        for bank in studio.get_bank_list(count)? {
+            if bank.get_loading_state()? == LoadingState::Loaded {
+                loaded.push(bank);
+            }
+        }
+
+        // A reloaded bank may get the handle of the bank it replaced.
+        let banks_loaded = bank_loaded.read().count() > 0;
+
+        if !banks_loaded && !banks_changed(&loaded, &catalog.loaded) {
+            return Ok(());
+        }
+
+        let count = studio.get_parameter_description_count()?;
+
+        catalog.parameters = studio.get_parameter_description_list(count)?;
+        catalog.banks = loaded
+            .iter()
+            .filter_map(|bank| {
+                CatalogBank::describe(*bank)
+                    .inspect_err(|e| warn!("Could not add bank to the event catalog: {e}"))
+                    .ok()
+            })
+            .collect();
+        catalog.loaded = loaded;
+
+        Ok(())
+    }
+}
+
+/// The number of labels of a labeled parameter, or `0` if the parameter has no labels.
+///
+/// Labels are indexed from `0`, for the values from the minimum to the maximum of the parameter.
+pub fn label_count(description: &ParameterDescription) -> i32 {
+    if description.flags & FMOD_STUDIO_PARAMETER_LABELED == 0 {
+        return 0;
+    }
+
+    ((description.maximum - description.minimum) as i32 + 1).max(0)
+}
+
+/// Returns `true` if the loaded banks differ from the ones the catalog was built from.
+fn banks_changed(loaded: &[Bank], cataloged: &[Bank]) -> bool {
+    loaded.len() != cataloged.len()
+        || loaded
+            .iter()
+            .zip(cataloged)
+            .any(|(bank, cataloged)| bank.as_mut_ptr() != cataloged.as_mut_ptr())
+}
+
+fn user_property(property: UserProperty) -> (String, UserPropertyValue) {
+    // SAFETY: The type tells which field of the union is set, and string values are owned by
+    // FMOD for as long as the bank is loaded.
+    let value = unsafe {
+        match property.type_ {
+            UserPropertyType::Integer => UserPropertyValue::Integer(property.union.intvalue),
+            UserPropertyType::Boolean => UserPropertyValue::Boolean(property.union.boolvalue != 0),
+            UserPropertyType::Float => UserPropertyValue::Float(property.union.floatvalue),
+            UserPropertyType::String => UserPropertyValue::String(
+                CStr::from_ptr(property.union.stringvalue)
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+        }
+    };
+
+    (property.name, value)
+}
+
+/// Formats a GUID the way FMOD Studio does, e.g. `{b0bb0d5c-ac3b-4d7c-9a5e-2f1b6c4a7d3e}`.
+fn guid_string(guid: &Guid) -> String {
+    let [a, b, c, d, e, f, g, h] = guid.data_4;
+
+    format!(
+        "{{{:08x}-{:04x}-{:04x}-{a:02x}{b:02x}-{c:02x}{d:02x}{e:02x}{f:02x}{g:02x}{h:02x}}}",
+        guid.data_1, guid.data_2, guid.data_3
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::without_provenance_mut;
+
+    use libfmod::{ParameterId, ParameterType};
+
+    use super::*;
+
+    fn bank(address: usize) -> Bank {
+        Bank::from(without_provenance_mut(address))
+    }
+
+    #[test]
+    fn guids_are_formatted_like_fmod_studio() {
+        let guid = Guid {
+            data_1: 0xb0bb0d5c,
+            data_2: 0xac3b,
+            data_3: 0x4d7c,
+            data_4: [0x9a, 0x5e, 0x2f, 0x1b, 0x6c, 0x4a, 0x7d, 0x3e],
+        };
+
+        assert_eq!(guid_string(&guid), "{b0bb0d5c-ac3b-4d7c-9a5e-2f1b6c4a7d3e}");
+
+        let zero = Guid {
+            data_1: 0,
+            data_2: 0,
+            data_3: 0,
+            data_4: [0; 8],
+        };
+
+        assert_eq!(guid_string(&zero), "{00000000-0000-0000-0000-000000000000}");
+    }
+
+    fn parameter(flags: u32, minimum: f32, maximum: f32) -> ParameterDescription {
+        ParameterDescription {
+            name: "Location".to_string(),
+            id: ParameterId {
+                data_1: 1,
+                data_2: 2,
+            },
+            minimum,
+            maximum,
+            defaultvalue: minimum,
+            type_: ParameterType::GameControlled,
+            flags,
+            guid: Guid {
+                data_1: 0,
+                data_2: 0,
+                data_3: 0,
+                data_4: [0; 8],
+            },
+        }
+    }
+
+    #[test]
+    fn labeled_parameters_have_a_label_per_value() {
+        assert_eq!(
+            label_count(&parameter(FMOD_STUDIO_PARAMETER_LABELED, 0.0, 2.0)),
+            3
+        );
+        assert_eq!(
+            label_count(&parameter(FMOD_STUDIO_PARAMETER_LABELED, 1.0, 1.0)),
+            1
+        );
+        assert_eq!(label_count(&parameter(0, 0.0, 2.0)), 0);
+    }
+
+    #[test]
+    fn banks_change_when_loaded_unloaded_or_replaced() {
+        let cataloged = [bank(8), bank(16)];
+
+        assert!(!banks_changed(&[bank(8), bank(16)], &cataloged));
+        assert!(banks_changed(&[bank(8), bank(16), bank(24)], &cataloged));
+        assert!(banks_changed(&[bank(8)], &cataloged));
+        assert!(banks_changed(&[bank(8), bank(24)], &cataloged));
+        assert!(!banks_changed(&[], &[]));
+    }
+}
@@ -0,0 +1,179 @@
+//! Typed local parameters, derived from the fields of a component.
+//!
+//! Deriving [`FmodParameters`](macro@crate::prelude::FmodParameters) maps each field of a
+//! component to the local parameter of the same name at compile time. Adding the
+//! [`EventParametersPlugin`] for the component sends its fields to the [`AudioSource`] on the
+//! same entity whenever the component changes:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_fmod::prelude::*;
+//! #[derive(Component, FmodParameters)]
+//! struct Engine {
+//!     #[fmod(name = "RPM")]
+//!     rpm: f32,
+//!     load: f32,
+//! }
+//!
+//! App::new().add_plugins(EventParametersPlugin::<Engine>::default());
+//! ```
+//!
+//! The parameter IDs are looked up once per event instead of by name on every change. Parameters
+//! the event does not have are skipped with a warning.
+
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::error::Result;
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::log::warn;
+use bevy::prelude::{Changed, Component, IntoScheduleConfigs, Local, MessageReader, Or, Query};
+use libfmod::{EventDescription, Guid};
+
+use crate::bank_loading::BankLoaded;
+use crate::components::audio_source::AudioSource;
+use crate::components::fmod_parameters::{ParameterKey, ResolvedParameter};
+use crate::fmod_plugin::{FmodSystems, FmodUpdateSchedule};
+use crate::fmod_status::fmod_enabled;
+use crate::ids::guids_equal;
+
+/// A component whose fields are local parameters of the event on the same entity.
+///
+/// Implement it with `#[derive(FmodParameters)]` instead of by hand. See the
+/// [module documentation](self).
+pub trait EventParameters: Component {
+    /// The names of the parameters, in the order of their indices.
+    const PARAMETER_NAMES: &'static [&'static str];
+
+    /// Whether parameters jump to their new value instead of moving towards it with the seek
+    /// speed set in FMOD Studio.
+    const IGNORE_SEEK_SPEED: bool = false;
+
+    /// The value of the parameter at the given index of [`PARAMETER_NAMES`](Self::PARAMETER_NAMES).
+    fn parameter_value(&self, index: usize) -> f32;
+}
+
+/// A field type that can be used as a parameter value by `#[derive(FmodParameters)]`.
+///
+/// Implement it for your own types, e.g. enums matching the labels of a labeled parameter.
+pub trait ParameterValue {
+    /// The value of the parameter.
+    fn parameter_value(&self) -> f32;
+}
+
+macro_rules! impl_parameter_value {
+    ($($ty:ty),*) => {$(
+        impl ParameterValue for $ty {
+            fn parameter_value(&self) -> f32 {
+                *self as f32
+            }
+        }
+    )*};
+}
+
+impl_parameter_value!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl ParameterValue for bool {
+    fn parameter_value(&self) -> f32 {
+        if *self { 1.0 } else { 0.0 }
+    }
+}
+
+/// Sends the fields of the component `T` to the [`AudioSource`] on the same entity whenever `T`
+/// changes, in the [`FmodPlugin::update_schedule`](crate::FmodPlugin::update_schedule). See the
+/// [module documentation](self).
+pub struct EventParametersPlugin<T>(PhantomData<T>);
+
+impl<T> Default for EventParametersPlugin<T> {
+    fn default() -> Self {
+        EventParametersPlugin(PhantomData)
+    }
+}
+
+impl<T: EventParameters> Plugin for EventParametersPlugin<T> {
+    fn build(&self, _app: &mut App) {}
+
+    // The update schedule is only known once the `FmodPlugin` was built.
+    fn finish(&self, app: &mut App) {
+        let schedule = app
+            .world()
+            .get_resource::<FmodUpdateSchedule>()
+            .map_or(PostUpdate.intern(), |schedule| schedule.0);
+
+        app.add_systems(
+            schedule,
+            sync_event_parameters::<T>
+                .before(FmodSystems::Update)
+                .run_if(fmod_enabled),
+        );
+    }
+}
+
+/// Parameters need to be sent to FMOD when they changed or the event instance was replaced.
+type ParametersOutdated<T> = Or<(Changed<T>, Changed<AudioSource>)>;
+
+/// The resolved parameters of `T`, by the ID of the event they were resolved for.
+type ResolvedEvents = Vec<(Guid, Vec<Option<ResolvedParameter>>)>;
+
+fn sync_event_parameters<T: EventParameters>(
+    query: Query<(&AudioSource, &T), ParametersOutdated<T>>,
+    mut resolved_events: Local<ResolvedEvents>,
+    mut bank_loaded: MessageReader<BankLoaded>,
+) -> Result {
+    // A reloaded bank may assign new IDs to the parameters of its events.
+    if bank_loaded.read().count() > 0 {
+        resolved_events.clear();
+    }
+
+    for (audio_source, parameters) in query.iter() {
+        if !audio_source.is_valid() {
+            continue;
+        }
+
+        let description = audio_source.get_description()?;
+        let event_id = description.get_id()?;
+
+        let index = match resolved_events
+            .iter()
+            .position(|(resolved, _)| guids_equal(resolved, &event_id))
+        {
+            Some(index) => index,
+            None => {
+                resolved_events.push((event_id, resolve::<T>(description)));
+                resolved_events.len() - 1
+            }
+        };
+
+        for (index, parameter) in resolved_events[index].1.iter().enumerate() {
+            let Some(parameter) = parameter else {
+                continue;
+            };
+
+            let value = parameters.parameter_value(index);
+            let event_instance = audio_source.event_instance;
+
+            if let Err(e) = parameter.set(event_instance, value, T::IGNORE_SEEK_SPEED) {
+                let name = T::PARAMETER_NAMES[index];
+                warn!("Could not set parameter {name} to {value}: {e}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn resolve<T: EventParameters>(description: EventDescription) -> Vec<Option<ResolvedParameter>> {
+    T::PARAMETER_NAMES
+        .iter()
+        .map(|name| {
+            ResolvedParameter::resolve(description, &ParameterKey::from(*name))
+                .inspect_err(|e| {
+                    warn!(
+                        "Event has no parameter {name} of {}: {e}",
+                        std::any::type_name::<T>()
+                    );
+                })
+                .ok()
+        })
+        .collect()
+}
@@ -0,0 +1,88 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{Asset, AssetLoader, LoadContext};
+use bevy::log::{debug, error};
+use bevy::reflect::TypePath;
+use libfmod::ffi::FMOD_STUDIO_LOAD_BANK_NORMAL;
+use libfmod::{Bank, Studio};
+
+/// An FMOD bank loaded through the [`AssetServer`](bevy::asset::AssetServer).
+///
+/// Loading banks as assets resolves their paths relative to the assets folder and ties their
+/// lifetime to their handles: the bank is unloaded from FMOD once the last strong
+/// [`Handle<FmodBank>`](bevy::asset::Handle) is dropped. This allows loading and unloading banks
+/// per level instead of once at startup.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::prelude::*;
+/// #[derive(Resource)]
+/// struct LevelBanks(Vec<Handle<FmodBank>>);
+///
+/// fn load_level_banks(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.insert_resource(LevelBanks(vec![
+///         asset_server.load("audio/Forest.bank"),
+///         asset_server.load("audio/Forest.assets.bank"),
+///     ]));
+/// }
+/// ```
+///
+/// Make sure the master bank and its strings bank are loaded before any event is used, either
+/// through [`FmodPlugin::audio_banks_paths`](crate::FmodPlugin::audio_banks_paths) or as assets.
+#[derive(Asset, TypePath, Debug)]
+pub struct FmodBank {
+    bank: Bank,
+}
+
+impl FmodBank {
+    /// The underlying FMOD [`Bank`].
+    pub fn bank(&self) -> Bank {
+        self.bank
+    }
+}
+
+impl Drop for FmodBank {
+    fn drop(&mut self) {
+        // The handle is invalid if FMOD was released before the asset.
+        if !self.bank.is_valid() {
+            return;
+        }
+
+        if let Err(e) = self.bank.unload() {
+            error!("Could not unload bank: {e}");
+        }
+    }
+}
+
+/// Loads `.bank` files as [`FmodBank`] assets.
+#[derive(TypePath)]
+pub(crate) struct FmodBankLoader {
+    pub(crate) studio: Studio,
+}
+
+impl AssetLoader for FmodBankLoader {
+    type Asset = FmodBank;
+    type Settings = ();
+    type Error = crate::error::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> crate::Result<FmodBank> {
+        debug!("Loading audio bank asset: {:?}", load_context.path());
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let bank = self
+            .studio
+            .load_bank_memory(&bytes, FMOD_STUDIO_LOAD_BANK_NORMAL)?;
+
+        Ok(FmodBank { bank })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bank"]
+    }
+}
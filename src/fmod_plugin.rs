@@ -1,16 +1,50 @@
+#[cfg(feature = "assets")]
+use bevy::asset::{AssetApp, AssetServer};
 use bevy::ecs::error::Result;
-use bevy::ecs::lifecycle::Remove;
+use bevy::ecs::intern::Interned;
+use bevy::ecs::lifecycle::Replace;
 use bevy::ecs::observer::On;
+use bevy::ecs::schedule::ScheduleLabel;
 use bevy::ecs::system::Query;
-use bevy::log::error;
-use bevy::prelude::{App, Plugin, PostUpdate, Res, Update};
+#[cfg(feature = "assets")]
+use bevy::log::debug;
+use bevy::log::warn;
+use bevy::prelude::{
+    App, IntoScheduleConfigs, Plugin, PostUpdate, PreUpdate, Res, Resource, SystemSet, Update,
+    resource_exists,
+};
 
+use crate::bank_loading::{
+    BankLoadFailed, BankLoaded, BankLoadingProgress, load_banks, update_bank_loading,
+};
 use crate::components::audio_listener::AudioListener;
 use crate::components::audio_source::AudioSource;
+use crate::components::fading_out::FadingOut;
+use crate::components::finish_behavior::FinishBehavior;
+use crate::components::fmod_event::{FmodEvent, FmodEventLookupFailed};
+use crate::components::fmod_parameters::FmodParameters;
+use crate::components::music_clock::MusicClock;
+use crate::components::parameter_bindings::ParameterBindings;
+use crate::components::parameter_tweens::ParameterTweens;
+use crate::components::playback_status::FmodPlaybackStatus;
 use crate::components::velocity::VelocityPlugin;
+use crate::event_callbacks::{
+    CallbackQueue, FmodCallback, deliver_callbacks, on_insert_audio_source,
+    register_replaced_instances,
+};
+use crate::event_catalog::FmodEventCatalog;
+#[cfg(feature = "assets")]
+use crate::fmod_bank::{FmodBank, FmodBankLoader};
+use crate::fmod_settings::FmodSettings;
+use crate::fmod_status::{FmodStatus, fmod_enabled};
 use crate::fmod_studio::FmodStudio;
+use crate::global_parameters::GlobalParameters;
+use crate::one_shot::AttachedOneShots;
+use crate::validation::AudioReferences;
 
 /// Initializes the FMOD Studio API and provides systems to update the audio sources and listeners.
+///
+/// If FMOD can't be initialized, audio is disabled instead of failing. See [`FmodStatus`].
 pub struct FmodPlugin {
     /// Paths to the audio banks which are usually in the Build folder of the FMOD project.
     pub audio_banks_paths: &'static [&'static str],
@@ -18,29 +52,143 @@ pub struct FmodPlugin {
     /// Optionally you can provide paths to FMOD plugins which will then be loaded automatically.
     /// For more information see: <https://www.fmod.com/docs/2.01/api/core-guide.html#dynamic>
     pub plugin_paths: Option<&'static [&'static str]>,
+
+    /// Settings used to initialize FMOD. See [`FmodSettings`].
+    pub settings: FmodSettings,
+
+    /// The schedule in which the FMOD Studio system is updated. Defaults to [`PostUpdate`].
+    pub update_schedule: Interned<dyn ScheduleLabel>,
 }
 
+/// System sets of the [`FmodPlugin`]. Use them to order your own systems relative to FMOD.
+///
+/// All sets only run while FMOD is [enabled](crate::fmod_status::fmod_enabled).
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FmodSystems {
+    /// Delivers the [`FmodCallback`]s received since the last frame and reacts to them, e.g. by
+    /// applying the [`FinishBehavior`] of stopped events. Runs in [`PreUpdate`].
+    Callbacks,
+    /// Updates the 3D attributes of audio sources and listeners. Runs in [`Update`].
+    UpdateAttributes,
+    /// Updates the FMOD Studio system. Runs in the [`FmodPlugin::update_schedule`].
+    ///
+    /// When using a non-realtime [`Output`](crate::Output), every run advances the mix by exactly
+    /// one DSP buffer.
+    Update,
+}
+
+/// The [`FmodPlugin::update_schedule`], for plugins that add systems to it.
+#[derive(Resource, Debug, Clone, Copy)]
+pub(crate) struct FmodUpdateSchedule(pub(crate) Interned<dyn ScheduleLabel>);
+
 impl Plugin for FmodPlugin {
     fn build(&self, app: &mut App) {
-        let studio_instance = match FmodStudio::new(self.audio_banks_paths, self.plugin_paths) {
-            Ok(instance) => instance,
+        app.insert_resource(FmodUpdateSchedule(self.update_schedule))
+            .add_message::<BankLoaded>()
+            .add_message::<BankLoadFailed>()
+            .add_message::<FmodEventLookupFailed>()
+            .add_message::<FmodCallback>()
+            .register_type::<FmodEvent>()
+            .register_type::<FinishBehavior>()
+            .init_resource::<BankLoadingProgress>()
+            .init_resource::<CallbackQueue>()
+            .init_resource::<GlobalParameters>()
+            .init_resource::<FmodEventCatalog>();
+
+        match FmodStudio::new(self.plugin_paths, &self.settings) {
+            Ok(instance) => {
+                load_banks(app, &instance, self.audio_banks_paths, &self.settings);
+                app.insert_resource(instance)
+                    .insert_resource(FmodStatus::Enabled);
+            }
             Err(e) => {
-                error!("Could not create FMOD studio API instance: {e}");
-                return;
+                warn!("Could not create FMOD studio API instance, audio is disabled: {e}");
+                app.insert_resource(FmodStatus::Disabled {
+                    reason: e.to_string(),
+                });
             }
-        };
+        }
 
         app.add_plugins(VelocityPlugin)
-            .insert_resource(studio_instance)
+            .configure_sets(PreUpdate, FmodSystems::Callbacks.run_if(fmod_enabled))
+            .configure_sets(Update, FmodSystems::UpdateAttributes.run_if(fmod_enabled))
+            .configure_sets(
+                self.update_schedule,
+                FmodSystems::Update.run_if(fmod_enabled),
+            )
             .add_systems(
                 Update,
                 (
                     AudioSource::update_3d_attributes,
                     AudioListener::update_3d_attributes,
+                    AttachedOneShots::update_3d_attributes,
+                )
+                    .in_set(FmodSystems::UpdateAttributes),
+            )
+            .add_systems(
+                self.update_schedule,
+                (
+                    (
+                        ParameterBindings::evaluate.before(FmodParameters::sync),
+                        FmodParameters::sync,
+                        GlobalParameters::sync,
+                    )
+                        .before(FmodSystems::Update)
+                        .run_if(fmod_enabled),
+                    // Records the values of the frame before global parameters are sent.
+                    AudioReferences::record
+                        .after(ParameterBindings::evaluate)
+                        .before(GlobalParameters::sync)
+                        .run_if(resource_exists::<AudioReferences>),
+                    Self::update.in_set(FmodSystems::Update),
+                ),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    update_bank_loading,
+                    (
+                        FmodEventCatalog::refresh.after(update_bank_loading),
+                        FmodPlaybackStatus::update,
+                    )
+                        .run_if(fmod_enabled),
+                    (
+                        register_replaced_instances,
+                        deliver_callbacks,
+                        (MusicClock::update, FinishBehavior::apply),
+                    )
+                        .chain()
+                        .in_set(FmodSystems::Callbacks),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    FadingOut::despawn_stopped,
+                    ParameterTweens::advance.run_if(fmod_enabled),
                 ),
             )
-            .add_systems(PostUpdate, Self::update)
-            .add_observer(on_remove_audio_source);
+            .add_observer(on_replace_audio_source)
+            .add_observer(on_insert_audio_source)
+            .add_observer(AudioReferences::on_insert_audio_source)
+            .add_observer(FmodEvent::on_insert);
+    }
+
+    // Plugins added after this one, like the `AssetPlugin`, are only built by now.
+    #[cfg(feature = "assets")]
+    fn finish(&self, app: &mut App) {
+        if !app.world().contains_resource::<AssetServer>() {
+            debug!("No AssetServer found, FmodBank assets are not available");
+            return;
+        }
+
+        app.init_asset::<FmodBank>();
+
+        // Without FMOD, loading a bank fails because there is no loader for it.
+        if let Some(studio) = app.world().get_resource::<FmodStudio>() {
+            let studio = studio.0;
+            app.register_asset_loader(FmodBankLoader { studio });
+        }
     }
 }
 
@@ -50,22 +198,55 @@ impl FmodPlugin {
         Ok(())
     }
 
+    /// Creates a new plugin which loads the given audio banks, using the default [`FmodSettings`].
     #[must_use]
     pub fn new(audio_banks_paths: &'static [&'static str]) -> Self {
         FmodPlugin {
             audio_banks_paths,
             plugin_paths: None,
+            settings: FmodSettings::default(),
+            update_schedule: PostUpdate.intern(),
         }
     }
+
+    /// Sets the paths to FMOD plugins which will be loaded automatically.
+    #[must_use]
+    pub fn with_plugin_paths(mut self, plugin_paths: &'static [&'static str]) -> Self {
+        self.plugin_paths = Some(plugin_paths);
+        self
+    }
+
+    /// Sets the settings used to initialize FMOD.
+    #[must_use]
+    pub fn with_settings(mut self, settings: FmodSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Sets the schedule in which the FMOD Studio system is updated.
+    ///
+    /// Combined with a non-realtime [`Output`](crate::Output), updating in a fixed schedule such
+    /// as `FixedPostUpdate` makes the mix advance deterministically with simulated time.
+    #[must_use]
+    pub fn with_update_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.update_schedule = schedule.intern();
+        self
+    }
 }
 
-fn on_remove_audio_source(
-    remove: On<Remove, AudioSource>,
+/// Stops and releases the event instance when the [`AudioSource`] is removed or replaced.
+fn on_replace_audio_source(
+    replace: On<Replace, AudioSource>,
     query: Query<&mut AudioSource>,
 ) -> Result {
-    let audio_source = query.get(remove.entity)?;
+    let audio_source = query.get(replace.entity)?;
     let event_instance = audio_source.event_instance;
 
+    // The instance was already released if its bank was unloaded.
+    if !event_instance.is_valid() {
+        return Ok(());
+    }
+
     event_instance.stop(audio_source.despawn_stop_mode)?;
     event_instance.release()?;
 
@@ -0,0 +1,317 @@
+use std::ffi::CString;
+use std::path::PathBuf;
+
+use libfmod::ffi::{
+    FMOD_INIT_3D_RIGHTHANDED, FMOD_INITFLAGS, FMOD_STUDIO_INIT_NORMAL, FMOD_STUDIO_INITFLAGS,
+    FMOD_STUDIO_LOAD_BANK_FLAGS, FMOD_STUDIO_LOAD_BANK_NONBLOCKING, FMOD_STUDIO_LOAD_BANK_NORMAL,
+};
+use libfmod::{OutputType, SpeakerMode, Studio, System};
+
+use crate::error::Error;
+
+/// Settings used to initialize the FMOD Studio and Core systems.
+///
+/// The defaults match what FMOD recommends for most games. Use the `with_*` methods to change
+/// individual settings and pass the result to [`FmodPlugin::with_settings`](crate::FmodPlugin::with_settings).
+///
+/// ```no_run
+/// # use bevy_fmod::prelude::*;
+/// # use bevy_fmod::libfmod::SpeakerMode;
+/// let settings = FmodSettings::default()
+///     .with_max_channels(256)
+///     .with_software_format(48_000, SpeakerMode::Stereo);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FmodSettings {
+    /// The maximum number of channels FMOD will mix. Defaults to `1024`.
+    pub max_channels: i32,
+    /// FMOD Studio initialization flags. Defaults to `FMOD_STUDIO_INIT_NORMAL`.
+    ///
+    /// `FMOD_STUDIO_INIT_LIVEUPDATE` is added automatically when the `live-update` feature is
+    /// enabled.
+    pub studio_flags: FMOD_STUDIO_INITFLAGS,
+    /// FMOD Core initialization flags. Defaults to `FMOD_INIT_3D_RIGHTHANDED`, which matches
+    /// Bevy's coordinate system.
+    pub core_flags: FMOD_INITFLAGS,
+    /// Where FMOD should send the mixed audio. Defaults to [`Output::Default`].
+    pub output: Output,
+    /// The sample rate and speaker mode of the software mixer. When `None`, FMOD's defaults are
+    /// used.
+    pub software_format: Option<SoftwareFormat>,
+    /// The size of the DSP mixing buffer. When `None`, FMOD's defaults are used.
+    pub dsp_buffer_size: Option<DspBufferSize>,
+    /// Advanced settings of the FMOD Studio system.
+    pub advanced_settings: AdvancedSettings,
+    /// How the banks passed to the plugin are loaded. Defaults to [`BankLoading::Blocking`].
+    pub bank_loading: BankLoading,
+    /// Whether the sample data of the banks passed to the plugin is loaded right away instead of
+    /// when an event is first played. Defaults to `false`.
+    pub load_sample_data: bool,
+}
+
+impl Default for FmodSettings {
+    fn default() -> Self {
+        FmodSettings {
+            max_channels: 1024,
+            studio_flags: FMOD_STUDIO_INIT_NORMAL,
+            core_flags: FMOD_INIT_3D_RIGHTHANDED,
+            output: Output::Default,
+            software_format: None,
+            dsp_buffer_size: None,
+            advanced_settings: AdvancedSettings::default(),
+            bank_loading: BankLoading::Blocking,
+            load_sample_data: false,
+        }
+    }
+}
+
+impl FmodSettings {
+    /// Sets the maximum number of channels FMOD will mix.
+    #[must_use]
+    pub fn with_max_channels(mut self, max_channels: i32) -> Self {
+        self.max_channels = max_channels;
+        self
+    }
+
+    /// Sets the FMOD Studio initialization flags.
+    #[must_use]
+    pub fn with_studio_flags(mut self, studio_flags: FMOD_STUDIO_INITFLAGS) -> Self {
+        self.studio_flags = studio_flags;
+        self
+    }
+
+    /// Sets the FMOD Core initialization flags.
+    ///
+    /// Make sure to keep `FMOD_INIT_3D_RIGHTHANDED` unless you convert positions yourself.
+    #[must_use]
+    pub fn with_core_flags(mut self, core_flags: FMOD_INITFLAGS) -> Self {
+        self.core_flags = core_flags;
+        self
+    }
+
+    /// Sets where FMOD should send the mixed audio.
+    #[must_use]
+    pub fn with_output(mut self, output: Output) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Sets the FMOD output type. Shorthand for [`with_output`](Self::with_output) with
+    /// [`Output::Type`].
+    #[must_use]
+    pub fn with_output_type(self, output_type: OutputType) -> Self {
+        self.with_output(Output::Type(output_type))
+    }
+
+    /// Sets the sample rate and speaker mode of the software mixer.
+    #[must_use]
+    pub fn with_software_format(mut self, sample_rate: i32, speaker_mode: SpeakerMode) -> Self {
+        self.software_format = Some(SoftwareFormat {
+            sample_rate,
+            speaker_mode,
+            num_raw_speakers: None,
+        });
+        self
+    }
+
+    /// Sets the length of a single DSP buffer in samples and the number of buffers.
+    #[must_use]
+    pub fn with_dsp_buffer_size(mut self, buffer_length: u32, num_buffers: i32) -> Self {
+        self.dsp_buffer_size = Some(DspBufferSize {
+            buffer_length,
+            num_buffers,
+        });
+        self
+    }
+
+    /// Sets the advanced settings of the FMOD Studio system.
+    #[must_use]
+    pub fn with_advanced_settings(mut self, advanced_settings: AdvancedSettings) -> Self {
+        self.advanced_settings = advanced_settings;
+        self
+    }
+
+    /// Sets how the banks passed to the plugin are loaded.
+    #[must_use]
+    pub fn with_bank_loading(mut self, bank_loading: BankLoading) -> Self {
+        self.bank_loading = bank_loading;
+        self
+    }
+
+    /// Sets whether the sample data of the banks passed to the plugin is loaded right away.
+    ///
+    /// The sample data is included in the progress of the
+    /// [`BankLoadingProgress`](crate::bank_loading::BankLoadingProgress).
+    #[must_use]
+    pub fn with_sample_data_loading(mut self, load_sample_data: bool) -> Self {
+        self.load_sample_data = load_sample_data;
+        self
+    }
+
+    /// Applies the settings that have to be set on the Core system before initialization.
+    pub(crate) fn apply_core(&self, core: System) -> crate::Result<()> {
+        if let Some(output_type) = self.output.output_type() {
+            core.set_output(output_type)?;
+        }
+
+        if let Some(format) = &self.software_format {
+            core.set_software_format(
+                Some(format.sample_rate),
+                Some(format.speaker_mode),
+                format.num_raw_speakers,
+            )?;
+        }
+
+        if let Some(dsp_buffer_size) = &self.dsp_buffer_size {
+            core.set_dsp_buffer_size(dsp_buffer_size.buffer_length, dsp_buffer_size.num_buffers)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How banks are loaded. See [`FmodSettings::with_bank_loading`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BankLoading {
+    /// Banks are loaded while the app is built, blocking the main thread until they are loaded.
+    #[default]
+    Blocking,
+    /// Banks are loaded in the background. Track their progress with the
+    /// [`BankLoadingProgress`](crate::bank_loading::BankLoadingProgress) resource or the
+    /// [`BankLoaded`](crate::bank_loading::BankLoaded) and
+    /// [`BankLoadFailed`](crate::bank_loading::BankLoadFailed) messages.
+    NonBlocking,
+}
+
+impl BankLoading {
+    pub(crate) fn flags(self) -> FMOD_STUDIO_LOAD_BANK_FLAGS {
+        match self {
+            BankLoading::Blocking => FMOD_STUDIO_LOAD_BANK_NORMAL,
+            BankLoading::NonBlocking => FMOD_STUDIO_LOAD_BANK_NONBLOCKING,
+        }
+    }
+}
+
+/// Where FMOD sends the mixed audio. See [`FmodSettings::with_output`].
+///
+/// The non-realtime outputs only mix when the Studio system is updated, so every update of the
+/// [`FmodStudio`](crate::FmodStudio) advances the mix by exactly one DSP buffer.
+/// This makes them useful for headless tests and CI machines without a sound card. When one of
+/// them is selected, `FMOD_STUDIO_INIT_SYNCHRONOUS_UPDATE` is added to the studio flags so that
+/// the update happens on the calling thread.
+#[derive(Clone, Debug, Default)]
+pub enum Output {
+    /// Let FMOD pick the default output of the platform.
+    #[default]
+    Default,
+    /// Use the given FMOD output type.
+    Type(OutputType),
+    /// Mix in real time without playing any audio.
+    NoSound,
+    /// Mix in non-realtime without playing any audio.
+    NoSoundNrt,
+    /// Mix in non-realtime and write the result to the WAV file at the given path.
+    WavWriterNrt(PathBuf),
+}
+
+impl Output {
+    fn output_type(&self) -> Option<OutputType> {
+        match self {
+            Output::Default => None,
+            Output::Type(output_type) => Some(*output_type),
+            Output::NoSound => Some(OutputType::NoSound),
+            Output::NoSoundNrt => Some(OutputType::NoSoundNrt),
+            Output::WavWriterNrt(_) => Some(OutputType::WavWriterNrt),
+        }
+    }
+
+    /// Whether the output only mixes when the Studio system is updated.
+    pub(crate) fn is_non_realtime(&self) -> bool {
+        matches!(
+            self.output_type(),
+            Some(OutputType::NoSoundNrt | OutputType::WavWriterNrt)
+        )
+    }
+
+    /// The data passed to the output driver on initialization. For the WAV writer this is the
+    /// path of the file to write to.
+    pub(crate) fn driver_data(&self) -> crate::Result<Option<CString>> {
+        match self {
+            Output::WavWriterNrt(path) => {
+                let path = path
+                    .to_str()
+                    .ok_or_else(|| Error::NonUnicodePath(path.clone()))?;
+                Ok(Some(CString::new(path)?))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// The format of FMOD's software mixer. See [`FmodSettings::with_software_format`].
+#[derive(Clone, Debug)]
+pub struct SoftwareFormat {
+    /// The sample rate of the mixer in Hz.
+    pub sample_rate: i32,
+    /// The speaker setup of the mixer.
+    pub speaker_mode: SpeakerMode,
+    /// The number of speakers when using `SpeakerMode::Raw`.
+    pub num_raw_speakers: Option<i32>,
+}
+
+/// The size of FMOD's DSP mixing buffer. See [`FmodSettings::with_dsp_buffer_size`].
+#[derive(Clone, Debug)]
+pub struct DspBufferSize {
+    /// The length of a single buffer in samples.
+    pub buffer_length: u32,
+    /// The number of buffers.
+    pub num_buffers: i32,
+}
+
+/// Advanced settings of the FMOD Studio system. Every setting left at `None` keeps FMOD's default.
+///
+/// See <https://www.fmod.com/docs/2.02/api/studio-api-system.html#fmod_studio_advancedsettings>.
+#[derive(Clone, Debug, Default)]
+pub struct AdvancedSettings {
+    /// The size of the command queue in bytes.
+    pub command_queue_size: Option<u32>,
+    /// The initial size of the handle pool.
+    pub handle_initial_size: Option<u32>,
+    /// The update period of the Studio system in milliseconds.
+    pub studio_update_period: Option<i32>,
+    /// The size of the idle sample data pool in bytes.
+    pub idle_sample_data_pool_size: Option<i32>,
+    /// The delay for scheduling streams in samples.
+    pub streaming_schedule_delay: Option<u32>,
+}
+
+impl AdvancedSettings {
+    /// Applies the advanced settings to the Studio system. Must happen before initialization.
+    pub(crate) fn apply(&self, studio: &Studio) -> crate::Result<()> {
+        let mut settings = studio.get_advanced_settings()?;
+
+        if let Some(command_queue_size) = self.command_queue_size {
+            settings.commandqueuesize = command_queue_size;
+        }
+
+        if let Some(handle_initial_size) = self.handle_initial_size {
+            settings.handleinitialsize = handle_initial_size;
+        }
+
+        if let Some(studio_update_period) = self.studio_update_period {
+            settings.studioupdateperiod = studio_update_period;
+        }
+
+        if let Some(idle_sample_data_pool_size) = self.idle_sample_data_pool_size {
+            settings.idlesampledatapoolsize = idle_sample_data_pool_size;
+        }
+
+        if let Some(streaming_schedule_delay) = self.streaming_schedule_delay {
+            settings.streamingscheduledelay = streaming_schedule_delay;
+        }
+
+        studio.set_advanced_settings(settings)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::{Res, Resource};
+
+/// Reports whether FMOD could be initialized. Always inserted by the
+/// [`FmodPlugin`](crate::FmodPlugin).
+///
+/// When initialization fails, no [`FmodStudio`](crate::FmodStudio) resource is inserted and all
+/// systems of this crate are skipped, so the game keeps running without audio. Use
+/// [`fmod_enabled`] as a run condition for your own systems that access the
+/// [`FmodStudio`](crate::FmodStudio).
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub enum FmodStatus {
+    /// FMOD was initialized successfully and the [`FmodStudio`](crate::FmodStudio) resource is
+    /// available.
+    Enabled,
+    /// FMOD could not be initialized and audio is disabled.
+    Disabled {
+        /// Why FMOD could not be initialized.
+        reason: String,
+    },
+}
+
+impl FmodStatus {
+    /// Returns `true` if FMOD was initialized successfully.
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, FmodStatus::Enabled)
+    }
+}
+
+/// Run condition that returns `true` if the [`FmodStatus`] is [`Enabled`](FmodStatus::Enabled),
+/// i.e. the [`FmodStudio`](crate::FmodStudio) resource is available.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::prelude::*;
+/// fn play_music(studio: Res<FmodStudio>) {
+///     // ...
+/// }
+///
+/// App::new().add_systems(Startup, play_music.run_if(fmod_enabled));
+/// ```
+pub fn fmod_enabled(status: Option<Res<FmodStatus>>) -> bool {
+    status.is_some_and(|status| status.is_enabled())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use bevy::MinimalPlugins;
+    use bevy::prelude::App;
+
+    use super::*;
+    use crate::fmod_plugin::FmodPlugin;
+    use crate::fmod_settings::{FmodSettings, Output};
+    use crate::fmod_studio::FmodStudio;
+
+    #[test]
+    fn app_keeps_running_when_initialization_fails() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            FmodPlugin::new(&[]).with_settings(FmodSettings {
+                // FMOD can't be initialized with a path that contains a nul byte.
+                output: Output::WavWriterNrt(PathBuf::from("render\0.wav")),
+                ..FmodSettings::default()
+            }),
+        ));
+
+        app.update();
+        app.update();
+
+        assert!(!app.world().resource::<FmodStatus>().is_enabled());
+        assert!(!app.world().contains_resource::<FmodStudio>());
+    }
+}
@@ -1,3 +1,4 @@
+use std::ffi::c_void;
 use std::fs::canonicalize;
 use std::path::Path;
 
@@ -5,10 +6,11 @@ use bevy::log::error;
 use bevy::prelude::{Deref, DerefMut, Resource, debug};
 #[cfg(feature = "live-update")]
 use libfmod::ffi::FMOD_STUDIO_INIT_LIVEUPDATE;
-use libfmod::ffi::{
-    FMOD_INIT_3D_RIGHTHANDED, FMOD_STUDIO_INIT_NORMAL, FMOD_STUDIO_LOAD_BANK_NORMAL,
-};
-use libfmod::{Studio, System};
+use libfmod::ffi::{FMOD_STUDIO_INIT_SYNCHRONOUS_UPDATE, FMOD_STUDIO_LOAD_BANK_FLAGS};
+use libfmod::{Bank, Studio, System};
+
+use crate::error::Error;
+use crate::fmod_settings::FmodSettings;
 
 /// A resource that wraps the `Studio` object from the FMOD library.
 ///
@@ -19,10 +21,10 @@ pub struct FmodStudio(pub Studio);
 
 impl FmodStudio {
     pub(crate) fn new(
-        banks_paths: &[&'static str],
         plugin_paths: Option<&[&'static str]>,
+        settings: &FmodSettings,
     ) -> crate::Result<Self> {
-        let studio = Self::init_studio()?;
+        let studio = Self::init_studio(settings)?;
         let studio_core = studio.get_core_system()?;
 
         if let Some(plugin_paths) = plugin_paths {
@@ -34,13 +36,6 @@ impl FmodStudio {
             });
         }
 
-        banks_paths
-            .iter()
-            .for_each(|bank_path| match canonicalize(Path::new(bank_path)) {
-                Ok(path) => Self::load_bank(&studio, path.as_path()),
-                Err(e) => error!("Failed to canonicalize {bank_path}: {e}"),
-            });
-
         Ok(FmodStudio(studio))
     }
 
@@ -55,29 +50,71 @@ impl FmodStudio {
         };
     }
 
-    fn load_bank(studio: &Studio, bank_path: &Path) {
+    /// Loads a bank from a path relative to the current working directory.
+    ///
+    /// With `FMOD_STUDIO_LOAD_BANK_NONBLOCKING`, this returns immediately and the bank has to be
+    /// polled with [`Bank::get_loading_state`].
+    pub(crate) fn load_bank(
+        &self,
+        bank_path: &str,
+        flags: FMOD_STUDIO_LOAD_BANK_FLAGS,
+    ) -> crate::Result<Bank> {
+        let bank_path = canonicalize(Path::new(bank_path))?;
+
         debug!("Loading audio banks from: {:?}", bank_path);
 
-        let filename = bank_path.to_str().expect("Path should be valid unicode");
-        let flags = FMOD_STUDIO_LOAD_BANK_NORMAL;
+        let filename = bank_path
+            .to_str()
+            .ok_or_else(|| Error::NonUnicodePath(bank_path.clone()))?;
 
-        if let Err(e) = studio.load_bank_file(filename, flags) {
-            error!("Could not load bank: {e}")
-        };
+        Ok(self.load_bank_file(filename, flags)?)
     }
 
-    fn init_studio() -> crate::Result<Studio> {
+    fn init_studio(settings: &FmodSettings) -> crate::Result<Studio> {
         let studio = Studio::create()?;
 
-        let studio_flags = FMOD_STUDIO_INIT_NORMAL;
+        // The studio is only handed out once initialized, so release it if that fails.
+        if let Err(e) = Self::initialize(studio, settings) {
+            if let Err(release_error) = studio.release() {
+                error!("Could not release FMOD studio: {release_error}");
+            }
+            return Err(e);
+        }
+
+        Ok(studio)
+    }
+
+    fn initialize(studio: Studio, settings: &FmodSettings) -> crate::Result<()> {
+        settings.apply_core(studio.get_core_system()?)?;
+        settings.advanced_settings.apply(&studio)?;
+
+        let mut studio_flags = settings.studio_flags;
 
         #[cfg(feature = "live-update")]
-        let studio_flags = studio_flags | FMOD_STUDIO_INIT_LIVEUPDATE;
+        {
+            studio_flags |= FMOD_STUDIO_INIT_LIVEUPDATE;
+        }
+
+        // Non-realtime outputs mix during the update call, which has to happen on our thread for
+        // the mix to advance deterministically with each update.
+        if settings.output.is_non_realtime() {
+            studio_flags |= FMOD_STUDIO_INIT_SYNCHRONOUS_UPDATE;
+        }
 
         debug!("Initializing FMOD studio with flags: {}", studio_flags);
 
-        studio.initialize(1024, studio_flags, FMOD_INIT_3D_RIGHTHANDED, None)?;
+        // Has to outlive the initialize call, FMOD only borrows the pointer.
+        let driver_data = settings.output.driver_data()?;
 
-        Ok(studio)
+        studio.initialize(
+            settings.max_channels,
+            studio_flags,
+            settings.core_flags,
+            driver_data
+                .as_ref()
+                .map(|driver_data| driver_data.as_ptr() as *mut c_void),
+        )?;
+
+        Ok(())
     }
 }
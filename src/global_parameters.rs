@@ -0,0 +1,314 @@
+//! Global parameters, which affect all events using them.
+//!
+//! The [`GlobalParameters`] resource lists every global parameter of the loaded banks. Values set
+//! through it are sent to FMOD at the end of the frame, and the values FMOD reports back are
+//! available for UI:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_fmod::prelude::*;
+//! fn enter_cave(mut parameters: ResMut<GlobalParameters>) {
+//!     parameters.set("Reverb", 0.8);
+//!     parameters.set_label("Location", "Cave");
+//! }
+//!
+//! fn show_weather(parameters: Res<GlobalParameters>) {
+//!     if let Some(intensity) = parameters.value("Weather") {
+//!         info!("Weather intensity: {intensity}");
+//!     }
+//! }
+//! ```
+
+use bevy::ecs::error::Result;
+use bevy::log::warn;
+use bevy::prelude::{DetectChangesMut, MessageReader, Res, ResMut, Resource};
+use libfmod::ParameterId;
+
+use crate::bank_loading::{BankLoaded, BankLoadingProgress};
+use crate::event_catalog::label_count;
+use crate::fmod_studio::FmodStudio;
+
+/// The global parameters of all loaded banks. See the [module documentation](self).
+#[derive(Resource, Debug, Default)]
+pub struct GlobalParameters {
+    /// Whether parameters jump to their new value instead of moving towards it with the seek
+    /// speed set in FMOD Studio. Defaults to `false`.
+    pub ignore_seek_speed: bool,
+    parameters: Vec<GlobalParameter>,
+    pending: Vec<(String, PendingValue)>,
+}
+
+/// A global parameter in the [`GlobalParameters`].
+#[derive(Debug, Clone)]
+pub struct GlobalParameter {
+    name: String,
+    id: ParameterId,
+    minimum: f32,
+    maximum: f32,
+    labels: Vec<String>,
+    value: f32,
+    final_value: f32,
+}
+
+impl GlobalParameter {
+    /// The name of the parameter as shown in FMOD Studio.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The ID of the parameter.
+    pub fn id(&self) -> ParameterId {
+        self.id.clone()
+    }
+
+    /// The minimum value of the parameter.
+    pub fn minimum(&self) -> f32 {
+        self.minimum
+    }
+
+    /// The maximum value of the parameter.
+    pub fn maximum(&self) -> f32 {
+        self.maximum
+    }
+
+    /// The labels of a labeled parameter, from its minimum to its maximum value.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// The value of the parameter as last reported by FMOD.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// The value of the parameter as last reported by FMOD, including automation and modulation.
+    pub fn final_value(&self) -> f32 {
+        self.final_value
+    }
+
+    /// The label of the current value of a labeled parameter.
+    pub fn label(&self) -> Option<&str> {
+        let index = (self.value - self.minimum).round();
+
+        if index < 0.0 {
+            return None;
+        }
+
+        self.labels.get(index as usize).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum PendingValue {
+    Value(f32),
+    Label(String),
+}
+
+impl GlobalParameters {
+    /// Sets the value of a global parameter. It is sent to FMOD at the end of the frame, clamped
+    /// to the range of the parameter.
+    ///
+    /// Parameters of banks that are still loading are set once the banks finished loading.
+    pub fn set(&mut self, name: impl Into<String>, value: f32) {
+        self.set_pending(name.into(), PendingValue::Value(value));
+    }
+
+    /// Sets a labeled global parameter to the value of the given label.
+    pub fn set_label(&mut self, name: impl Into<String>, label: impl Into<String>) {
+        self.set_pending(name.into(), PendingValue::Label(label.into()));
+    }
+
+    /// The global parameter with the given name.
+    pub fn get(&self, name: &str) -> Option<&GlobalParameter> {
+        self.parameters
+            .iter()
+            .find(|parameter| parameter.name == name)
+    }
+
+    /// The value of a global parameter as last reported by FMOD.
+    pub fn value(&self, name: &str) -> Option<f32> {
+        self.get(name).map(GlobalParameter::value)
+    }
+
+    /// The label of the current value of a labeled global parameter.
+    pub fn label(&self, name: &str) -> Option<&str> {
+        self.get(name)?.label()
+    }
+
+    /// Iterates over all global parameters.
+    pub fn iter(&self) -> impl Iterator<Item = &GlobalParameter> {
+        self.parameters.iter()
+    }
+
+    /// The values that were set but not yet sent to FMOD. Labels have no value.
+    pub(crate) fn pending_values(&self) -> impl Iterator<Item = (&str, Option<f32>)> {
+        self.pending.iter().map(|(name, value)| match value {
+            PendingValue::Value(value) => (name.as_str(), Some(*value)),
+            PendingValue::Label(_) => (name.as_str(), None),
+        })
+    }
+
+    fn set_pending(&mut self, name: String, value: PendingValue) {
+        match self
+            .pending
+            .iter_mut()
+            .find(|(pending, _)| *pending == name)
+        {
+            Some((_, pending)) => *pending = value,
+            None => self.pending.push((name, value)),
+        }
+    }
+
+    pub(crate) fn sync(
+        studio: Res<FmodStudio>,
+        progress: Res<BankLoadingProgress>,
+        mut bank_loaded: MessageReader<BankLoaded>,
+        mut global_parameters: ResMut<GlobalParameters>,
+    ) -> Result {
+        let count = studio.get_parameter_description_count()?;
+        let banks_loaded = bank_loaded.read().count() > 0;
+
+        // Global parameters are added and removed by loading and unloading banks, and their IDs
+        // may change when a bank is loaded again.
+        if banks_loaded || count as usize != global_parameters.parameters.len() {
+            global_parameters.parameters = Self::describe(&studio, count)?;
+        }
+
+        if !global_parameters.pending.is_empty() {
+            let global_parameters = global_parameters.bypass_change_detection();
+            let pending = std::mem::take(&mut global_parameters.pending);
+
+            for (name, value) in pending {
+                let Some(parameter) = global_parameters
+                    .parameters
+                    .iter()
+                    .find(|parameter| parameter.name == name)
+                else {
+                    if progress.is_finished() {
+                        warn!("There is no global parameter {name}");
+                    } else {
+                        global_parameters.pending.push((name, value));
+                    }
+
+                    continue;
+                };
+
+                let result = match &value {
+                    PendingValue::Value(value) => studio.set_parameter_by_id(
+                        parameter.id.clone(),
+                        value.clamp(parameter.minimum, parameter.maximum),
+                        global_parameters.ignore_seek_speed,
+                    ),
+                    PendingValue::Label(label) => studio.set_parameter_by_id_with_label(
+                        parameter.id.clone(),
+                        label,
+                        global_parameters.ignore_seek_speed,
+                    ),
+                };
+
+                if let Err(e) = result {
+                    warn!("Could not set global parameter {name} to {value:?}: {e}");
+                }
+            }
+        }
+
+        // Only mark the resource as changed if FMOD reports different values.
+        let mut changed = false;
+
+        for parameter in global_parameters
+            .bypass_change_detection()
+            .parameters
+            .iter_mut()
+        {
+            let (value, final_value) = studio.get_parameter_by_id(parameter.id.clone())?;
+
+            if (value, final_value) != (parameter.value, parameter.final_value) {
+                parameter.value = value;
+                parameter.final_value = final_value;
+                changed = true;
+            }
+        }
+
+        if changed {
+            global_parameters.set_changed();
+        }
+
+        Ok(())
+    }
+
+    fn describe(studio: &FmodStudio, count: i32) -> crate::Result<Vec<GlobalParameter>> {
+        studio
+            .get_parameter_description_list(count)?
+            .into_iter()
+            .map(|description| {
+                let labels = (0..label_count(&description))
+                    .map(|index| label(studio, &description.id, index))
+                    .collect::<crate::Result<_>>()?;
+
+                let (value, final_value) = studio.get_parameter_by_id(description.id.clone())?;
+
+                Ok(GlobalParameter {
+                    name: description.name,
+                    id: description.id,
+                    minimum: description.minimum,
+                    maximum: description.maximum,
+                    labels,
+                    value,
+                    final_value,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The label at the given index of a labeled global parameter.
+fn label(studio: &FmodStudio, id: &ParameterId, index: i32) -> crate::Result<String> {
+    // Without a buffer, FMOD only reports the size the label needs.
+    let (_, size) = studio.get_parameter_label_by_id(id.clone(), index, 0)?;
+    let (label, _) = studio.get_parameter_label_by_id(id.clone(), index, size)?;
+
+    Ok(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(value: f32) -> GlobalParameter {
+        GlobalParameter {
+            name: "Location".to_string(),
+            id: ParameterId {
+                data_1: 1,
+                data_2: 2,
+            },
+            minimum: 1.0,
+            maximum: 3.0,
+            labels: vec!["Forest".to_string(), "Cave".to_string(), "City".to_string()],
+            value,
+            final_value: value,
+        }
+    }
+
+    #[test]
+    fn label_matches_the_nearest_value() {
+        assert_eq!(location(1.0).label(), Some("Forest"));
+        assert_eq!(location(2.4).label(), Some("Cave"));
+        assert_eq!(location(3.0).label(), Some("City"));
+        assert_eq!(location(0.0).label(), None);
+        assert_eq!(location(5.0).label(), None);
+    }
+
+    #[test]
+    fn later_values_replace_pending_ones() {
+        let mut parameters = GlobalParameters::default();
+        parameters.set("Weather", 0.5);
+        parameters.set_label("Location", "Cave");
+        parameters.set("Weather", 1.0);
+
+        assert_eq!(parameters.pending.len(), 2);
+        assert!(matches!(
+            parameters.pending[0],
+            (ref name, PendingValue::Value(value)) if name == "Weather" && value == 1.0
+        ));
+    }
+}
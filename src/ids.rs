@@ -0,0 +1,13 @@
+//! Comparisons of the IDs of libfmod, which don't implement `PartialEq`.
+
+use libfmod::{Guid, ParameterId};
+
+/// Returns `true` if both GUIDs identify the same object.
+pub(crate) fn guids_equal(a: &Guid, b: &Guid) -> bool {
+    a.data_1 == b.data_1 && a.data_2 == b.data_2 && a.data_3 == b.data_3 && a.data_4 == b.data_4
+}
+
+/// Returns `true` if both IDs identify the same parameter.
+pub(crate) fn parameter_ids_equal(a: &ParameterId, b: &ParameterId) -> bool {
+    a.data_1 == b.data_1 && a.data_2 == b.data_2
+}
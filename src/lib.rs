@@ -3,18 +3,44 @@
 #![deny(clippy::unwrap_used, clippy::wildcard_imports)]
 
 mod attributes_3d;
+pub mod bank_loading;
 pub mod components;
 pub mod error;
+pub mod event_callbacks;
+pub mod event_catalog;
+pub mod event_parameters;
+#[cfg(feature = "assets")]
+#[doc(hidden)]
+pub mod fmod_bank;
 #[doc(hidden)]
 pub mod fmod_plugin;
 #[doc(hidden)]
+pub mod fmod_settings;
+#[doc(hidden)]
+pub mod fmod_status;
+#[doc(hidden)]
 pub mod fmod_studio;
+pub mod global_parameters;
+mod ids;
+pub mod one_shot;
 pub mod prelude;
+mod reflect_remote;
+pub mod studio_paths;
 #[cfg(feature = "utilities")]
 pub mod utilities;
+pub mod validation;
 
+#[cfg(feature = "assets")]
+#[doc(inline)]
+pub use fmod_bank::FmodBank;
+#[doc(inline)]
+pub use fmod_plugin::{FmodPlugin, FmodSystems};
+#[doc(inline)]
+pub use fmod_settings::{
+    AdvancedSettings, BankLoading, DspBufferSize, FmodSettings, Output, SoftwareFormat,
+};
 #[doc(inline)]
-pub use fmod_plugin::FmodPlugin;
+pub use fmod_status::{FmodStatus, fmod_enabled};
 #[doc(inline)]
 pub use fmod_studio::FmodStudio;
 
@@ -0,0 +1,274 @@
+//! Fire-and-forget playback of events through [`Commands`].
+//!
+//! One-shots are started and released immediately, so FMOD cleans them up once they finish
+//! playing. There is no entity to despawn and no [`AudioSource`](crate::prelude::AudioSource) to
+//! keep track of.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_fmod::prelude::*;
+//! fn explode(mut commands: Commands, player: Single<Entity, With<Camera>>) {
+//!     commands.play_one_shot(
+//!         OneShot::new("event:/Weapons/Explosion").with_parameter("Size", 2.0),
+//!         Transform::from_xyz(5.0, 0.0, 0.0),
+//!     );
+//!
+//!     commands.entity(*player).play_attached("event:/Character/Player Footsteps");
+//! }
+//! ```
+
+use bevy::log::{debug, warn};
+use bevy::math::Vec3;
+use bevy::prelude::{
+    ChildOf, Commands, Component, Entity, EntityCommands, EntityWorldMut, GlobalTransform, Query,
+    Transform, World,
+};
+use libfmod::ffi::FMOD_ERR_INVALID_HANDLE;
+use libfmod::{Attributes3d, Error, EventInstance};
+
+use crate::attributes_3d::attributes3d;
+use crate::components::velocity::Velocity;
+use crate::fmod_studio::FmodStudio;
+
+/// An event to play once. See [`FmodCommandsExt::play_one_shot`].
+///
+/// Can be created from the event path directly, e.g. `"event:/Weapons/Explosion".into()`.
+#[derive(Debug, Clone)]
+pub struct OneShot {
+    path: String,
+    parameters: Vec<(String, f32)>,
+}
+
+impl OneShot {
+    /// Creates a one-shot of the event with the given path.
+    pub fn new(path: impl Into<String>) -> Self {
+        OneShot {
+            path: path.into(),
+            parameters: Vec::new(),
+        }
+    }
+
+    /// Sets a local parameter of the event before it starts.
+    #[must_use]
+    pub fn with_parameter(mut self, name: impl Into<String>, value: f32) -> Self {
+        self.parameters.push((name.into(), value));
+        self
+    }
+
+    /// Creates, positions, starts and releases an instance of the event.
+    ///
+    /// Returns `None` and logs a warning if the event could not be played.
+    fn play(&self, world: &World, attributes: Attributes3d) -> Option<EventInstance> {
+        // Without FMOD there is nothing to play.
+        let studio = world.get_resource::<FmodStudio>()?;
+
+        match self.try_play(studio, attributes) {
+            Ok(event_instance) => Some(event_instance),
+            Err(e) => {
+                warn!("Could not play one-shot {}: {e}", self.path);
+                None
+            }
+        }
+    }
+
+    fn try_play(
+        &self,
+        studio: &FmodStudio,
+        attributes: Attributes3d,
+    ) -> crate::Result<EventInstance> {
+        let event_instance = studio.get_event(&self.path)?.create_instance()?;
+        let started = self.start(event_instance, attributes);
+
+        // A released instance stays valid until it finishes playing and is then destroyed by
+        // FMOD. If it never started, it is destroyed right away.
+        event_instance.release()?;
+
+        started.map(|()| event_instance)
+    }
+
+    fn start(&self, event_instance: EventInstance, attributes: Attributes3d) -> crate::Result<()> {
+        for (name, value) in &self.parameters {
+            event_instance.set_parameter_by_name(name, *value, true)?;
+        }
+
+        event_instance.set_3d_attributes(attributes)?;
+        event_instance.start()?;
+
+        Ok(())
+    }
+}
+
+impl From<&str> for OneShot {
+    fn from(path: &str) -> Self {
+        OneShot::new(path)
+    }
+}
+
+impl From<String> for OneShot {
+    fn from(path: String) -> Self {
+        OneShot::new(path)
+    }
+}
+
+/// Extension trait for playing one-shots through [`Commands`].
+pub trait FmodCommandsExt {
+    /// Plays an event once at the given position.
+    ///
+    /// The event keeps its position until it finishes. Use
+    /// [`FmodEntityCommandsExt::play_attached`] for events that should follow an entity.
+    fn play_one_shot(&mut self, one_shot: impl Into<OneShot>, transform: Transform);
+}
+
+impl FmodCommandsExt for Commands<'_, '_> {
+    fn play_one_shot(&mut self, one_shot: impl Into<OneShot>, transform: Transform) {
+        let one_shot = one_shot.into();
+
+        self.queue(move |world: &mut World| {
+            let attributes = attributes3d(
+                transform.translation,
+                Vec3::ZERO,
+                *transform.forward(),
+                *transform.up(),
+            );
+
+            one_shot.play(world, attributes);
+        });
+    }
+}
+
+/// Extension trait for playing one-shots attached to an entity through [`EntityCommands`].
+pub trait FmodEntityCommandsExt {
+    /// Plays an event once at the position of this entity, following the entity until the event
+    /// finishes or the entity is despawned.
+    fn play_attached(&mut self, one_shot: impl Into<OneShot>) -> &mut Self;
+}
+
+impl FmodEntityCommandsExt for EntityCommands<'_> {
+    fn play_attached(&mut self, one_shot: impl Into<OneShot>) -> &mut Self {
+        let one_shot = one_shot.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            let transform = global_transform(entity.world(), entity.id());
+            let velocity = entity
+                .get::<Velocity>()
+                .map_or(Vec3::ZERO, |velocity| velocity.current_velocity);
+
+            let attributes = attributes3d(
+                transform.translation(),
+                velocity,
+                *transform.forward(),
+                *transform.up(),
+            );
+
+            let Some(event_instance) = one_shot.play(entity.world(), attributes) else {
+                return;
+            };
+
+            match entity.get_mut::<AttachedOneShots>() {
+                Some(mut attached) => attached.0.push(event_instance),
+                None => {
+                    entity.insert(AttachedOneShots(vec![event_instance]));
+                }
+            }
+        })
+    }
+}
+
+/// The global transform of an entity, computed from its [`Transform`] and those of its ancestors.
+///
+/// The [`GlobalTransform`] of an entity that was just spawned is only updated by transform
+/// propagation, which runs after the commands of the frame were applied.
+fn global_transform(world: &World, entity: Entity) -> GlobalTransform {
+    let Some(transform) = world.get::<Transform>(entity) else {
+        return world
+            .get::<GlobalTransform>(entity)
+            .copied()
+            .unwrap_or_default();
+    };
+
+    let mut global = GlobalTransform::from(*transform);
+    let mut current = entity;
+
+    while let Some(parent) = world.get::<ChildOf>(current).map(ChildOf::parent) {
+        if let Some(transform) = world.get::<Transform>(parent) {
+            global = GlobalTransform::from(*transform) * global;
+        }
+        current = parent;
+    }
+
+    global
+}
+
+/// The released one-shots that follow an entity until they finish.
+#[derive(Component)]
+pub(crate) struct AttachedOneShots(Vec<EventInstance>);
+
+impl AttachedOneShots {
+    pub(crate) fn update_3d_attributes(
+        mut commands: Commands,
+        mut query: Query<(
+            Entity,
+            &mut AttachedOneShots,
+            &GlobalTransform,
+            Option<&Velocity>,
+        )>,
+    ) {
+        for (entity, mut attached, transform, vel_component) in query.iter_mut() {
+            // Released instances become invalid once they finished playing. Only mutate the
+            // component then, so it isn't marked as changed every frame.
+            if !attached.0.iter().all(EventInstance::is_valid) {
+                attached.0.retain(EventInstance::is_valid);
+            }
+
+            if attached.0.is_empty() {
+                commands.entity(entity).remove::<AttachedOneShots>();
+                continue;
+            }
+
+            let mut velocity = Vec3::ZERO;
+
+            if let Some(vel_component) = vel_component {
+                velocity = vel_component.current_velocity;
+            }
+
+            let attributes = attributes3d(
+                transform.translation(),
+                velocity,
+                *transform.forward(),
+                *transform.up(),
+            );
+
+            for event_instance in &attached.0 {
+                match event_instance.set_3d_attributes(attributes.clone()) {
+                    Ok(()) => {}
+                    // The one-shot finished since it was checked above.
+                    Err(Error::Fmod {
+                        code: FMOD_ERR_INVALID_HANDLE,
+                        ..
+                    }) => debug!("Attached one-shot finished while updating its position"),
+                    Err(e) => warn!("Could not update the position of an attached one-shot: {e}"),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_transform_includes_ancestors_before_propagation() {
+        let mut world = World::new();
+        let parent = world
+            .spawn(Transform::from_xyz(1.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)))
+            .id();
+        let child = world
+            .spawn((Transform::from_xyz(0.0, 1.0, 0.0), ChildOf(parent)))
+            .id();
+
+        let transform = global_transform(&world, child);
+
+        assert_eq!(transform.translation(), Vec3::new(1.0, 2.0, 0.0));
+    }
+}
@@ -6,11 +6,58 @@
 //! use bevy_fmod::prelude::*;
 //! ```
 
+pub use crate::bank_loading::BankLoadFailed;
+pub use crate::bank_loading::BankLoaded;
+pub use crate::bank_loading::BankLoadingProgress;
+pub use crate::bank_loading::banks_loaded;
 pub use crate::components::audio_listener::AudioListener;
 pub use crate::components::audio_source::AudioSource;
 pub use crate::components::bundles::SpatialAudioBundle;
 pub use crate::components::bundles::SpatialListenerBundle;
+pub use crate::components::fading_out::DespawnWithFadeoutExt;
+pub use crate::components::fading_out::FadingOut;
+pub use crate::components::finish_behavior::FinishBehavior;
+pub use crate::components::fmod_event::FmodEvent;
+pub use crate::components::fmod_event::FmodEventLookupFailed;
+pub use crate::components::fmod_parameters::FmodParameters;
+pub use crate::components::fmod_parameters::ParameterKey;
+pub use crate::components::music_clock::MusicClock;
+pub use crate::components::music_clock::on_beat;
+pub use crate::components::parameter_bindings::ParameterBinding;
+pub use crate::components::parameter_bindings::ParameterBindings;
+pub use crate::components::parameter_tweens::ParameterTween;
+pub use crate::components::parameter_tweens::ParameterTweenCompleted;
+pub use crate::components::parameter_tweens::ParameterTweens;
+pub use crate::components::parameter_tweens::TweenTarget;
+pub use crate::components::parameter_tweens::TweenTime;
+pub use crate::components::playback_status::FmodPlaybackStatus;
 pub use crate::components::velocity::Velocity;
+pub use crate::event_callbacks::FmodCallback;
+pub use crate::event_callbacks::FmodCallbackKind;
+pub use crate::event_catalog::FmodEventCatalog;
+pub use crate::event_parameters::EventParameters;
+pub use crate::event_parameters::EventParametersPlugin;
+pub use crate::event_parameters::ParameterValue;
+#[cfg(feature = "assets")]
+pub use crate::fmod_bank::FmodBank;
 pub use crate::fmod_plugin::FmodPlugin;
+pub use crate::fmod_plugin::FmodSystems;
+pub use crate::fmod_settings::BankLoading;
+pub use crate::fmod_settings::FmodSettings;
+pub use crate::fmod_status::FmodStatus;
+pub use crate::fmod_status::fmod_enabled;
 pub use crate::fmod_studio::FmodStudio;
+pub use crate::global_parameters::GlobalParameters;
+pub use crate::one_shot::FmodCommandsExt;
+pub use crate::one_shot::FmodEntityCommandsExt;
+pub use crate::one_shot::OneShot;
+pub use crate::studio_paths::BusPath;
+pub use crate::studio_paths::EventPath;
+pub use crate::studio_paths::ParameterName;
+pub use crate::studio_paths::VcaPath;
+pub use crate::validation::AudioReferences;
+pub use crate::validation::ValidationIssue;
+pub use crate::validation::ValidationReport;
+pub use bevy_fmod_macros::FmodParameters;
+pub use bevy_fmod_macros::fmod_event;
 pub use libfmod::StopMode;
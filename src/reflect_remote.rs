@@ -0,0 +1,11 @@
+//! Reflection for FMOD types used in reflected components.
+
+use bevy::reflect::reflect_remote;
+use libfmod::StopMode;
+
+#[reflect_remote(StopMode)]
+#[derive(Debug)]
+pub(crate) enum StopModeRemote {
+    AllowFadeout,
+    Immediate,
+}
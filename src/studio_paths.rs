@@ -0,0 +1,150 @@
+//! Typed paths of FMOD Studio objects.
+//!
+//! These are usually not written by hand but generated from the Studio project by
+//! [bevy_fmod_build](https://docs.rs/bevy_fmod_build) in a build script. A renamed or deleted
+//! event then fails to compile instead of failing to play at runtime:
+//!
+//! ```ignore
+//! mod fmod {
+//!     include!(concat!(env!("OUT_DIR"), "/fmod.rs"));
+//! }
+//!
+//! fn spawn_car(mut commands: Commands) {
+//!     commands.spawn((
+//!         FmodEvent::new(fmod::events::vehicles::CAR_ENGINE),
+//!         FmodParameters::new().with_parameter(fmod::events::vehicles::car_engine::RPM, 1000.0),
+//!     ));
+//! }
+//! ```
+//!
+//! Single event paths can also be checked with the
+//! [`fmod_event!`](crate::prelude::fmod_event) macro.
+//!
+//! Event, bus and VCA paths carry their GUID, which [`FmodStudio::event`],
+//! [`FmodStudio::bus`] and [`FmodStudio::vca`] use for the lookup.
+
+use libfmod::{Bus, EventDescription, Guid, Vca};
+
+use crate::components::fmod_parameters::ParameterKey;
+use crate::fmod_studio::FmodStudio;
+
+/// The path and GUID of an event or snapshot, e.g. `event:/Music/Level 01`.
+#[derive(Debug, Clone)]
+pub struct EventPath {
+    path: &'static str,
+    id: Guid,
+}
+
+/// The path and GUID of a bus, e.g. `bus:/SFX`.
+#[derive(Debug, Clone)]
+pub struct BusPath {
+    path: &'static str,
+    id: Guid,
+}
+
+/// The path and GUID of a VCA, e.g. `vca:/Music`.
+#[derive(Debug, Clone)]
+pub struct VcaPath {
+    path: &'static str,
+    id: Guid,
+}
+
+/// The name of a local or global parameter, with its range if it is known.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParameterName {
+    name: &'static str,
+    range: Option<(f32, f32)>,
+}
+
+macro_rules! impl_studio_path {
+    ($($ty:ident),*) => {$(
+        impl $ty {
+            /// Creates the path of an object with the given GUID.
+            pub const fn new(path: &'static str, id: Guid) -> Self {
+                $ty { path, id }
+            }
+
+            /// The full path.
+            pub const fn path(&self) -> &'static str {
+                self.path
+            }
+
+            /// The GUID.
+            pub fn id(&self) -> Guid {
+                self.id.clone()
+            }
+        }
+
+        impl From<$ty> for String {
+            fn from(path: $ty) -> Self {
+                path.path.to_string()
+            }
+        }
+    )*};
+}
+
+impl_studio_path!(EventPath, BusPath, VcaPath);
+
+impl ParameterName {
+    /// Creates the name of a parameter with an unknown range.
+    pub const fn new(name: &'static str) -> Self {
+        ParameterName { name, range: None }
+    }
+
+    /// Sets the range of values of the parameter.
+    #[must_use]
+    pub const fn with_range(mut self, minimum: f32, maximum: f32) -> Self {
+        self.range = Some((minimum, maximum));
+        self
+    }
+
+    /// The name.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The smallest value, if the range is known.
+    pub const fn minimum(&self) -> Option<f32> {
+        match self.range {
+            Some((minimum, _)) => Some(minimum),
+            None => None,
+        }
+    }
+
+    /// The largest value, if the range is known.
+    pub const fn maximum(&self) -> Option<f32> {
+        match self.range {
+            Some((_, maximum)) => Some(maximum),
+            None => None,
+        }
+    }
+}
+
+impl From<ParameterName> for String {
+    fn from(parameter: ParameterName) -> Self {
+        parameter.name.to_string()
+    }
+}
+
+impl From<ParameterName> for ParameterKey {
+    fn from(parameter: ParameterName) -> Self {
+        ParameterKey::Name(parameter.name.to_string())
+    }
+}
+
+impl FmodStudio {
+    /// Looks up an event or snapshot by its GUID.
+    pub fn event(&self, event: EventPath) -> crate::Result<EventDescription> {
+        Ok(self.get_event_by_id(event.id)?)
+    }
+
+    /// Looks up a bus by its GUID.
+    pub fn bus(&self, bus: BusPath) -> crate::Result<Bus> {
+        Ok(self.get_bus_by_id(bus.id)?)
+    }
+
+    /// Looks up a VCA by its GUID.
+    pub fn vca(&self, vca: VcaPath) -> crate::Result<Vca> {
+        Ok(self.get_vca_by_id(vca.id)?)
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+use bevy::app::{App, Plugin, Update};
+use bevy::log::{info, warn};
+use bevy::prelude::{
+    Entity, IntoScheduleConfigs, Local, MessageWriter, Query, Res, ResMut, SystemCondition,
+};
+use bevy::time::common_conditions::on_real_timer;
+use libfmod::ffi::{
+    FMOD_STUDIO_LOAD_BANK_NORMAL, FMOD_STUDIO_PARAMETER_AUTOMATIC, FMOD_STUDIO_PARAMETER_READONLY,
+};
+use libfmod::{Attributes3d, Bank, EventInstance, Guid, PlaybackState};
+
+use crate::FmodStudio;
+use crate::bank_loading::{BankLoaded, BankLoadingProgress};
+use crate::components::audio_source::AudioSource;
+use crate::event_callbacks::{CallbackQueue, register_callback};
+use crate::fmod_plugin::FmodSystems;
+use crate::fmod_status::fmod_enabled;
+use crate::ids::guids_equal;
+
+/// When this plugin is added, the banks passed to the [`FmodPlugin`](crate::FmodPlugin) are
+/// reloaded whenever their files change on disk, for example after rebuilding them in FMOD Studio.
+/// A changed bank is reloaded once its file stayed the same for one poll interval, so banks that are
+/// still being written are not picked up. A [`BankLoaded`] message is sent for every reloaded bank.
+///
+/// The event instance of every [`AudioSource`] playing an event of a reloaded bank is recreated.
+/// Its playback state, parameters, volume and timeline position are restored where possible. The
+/// parameter IDs cached by
+/// [`FmodParameters`](crate::components::fmod_parameters::FmodParameters) and the
+/// [`EventParametersPlugin`](crate::event_parameters::EventParametersPlugin) are looked up again.
+/// Banks that can't be unloaded are skipped with a warning.
+///
+/// This is meant for development and should not be added to release builds. Banks loaded as
+/// [`FmodBank`](crate::fmod_bank::FmodBank) assets are not watched.
+pub struct BankHotReloadPlugin {
+    /// How often the bank files are checked for changes. Defaults to one second.
+    pub poll_interval: Duration,
+}
+
+impl Default for BankHotReloadPlugin {
+    fn default() -> Self {
+        BankHotReloadPlugin {
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for BankHotReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            reload_changed_banks
+                .before(FmodSystems::UpdateAttributes)
+                .run_if(fmod_enabled.and(on_real_timer(self.poll_interval))),
+        );
+    }
+}
+
+/// The state of a bank file, for detecting changes.
+#[derive(Clone, Copy, PartialEq)]
+struct FileState {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileState {
+    fn read(path: &str) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+
+        Some(FileState {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+/// The bank files as they were last loaded, and changes that wait for the file to settle.
+#[derive(Default)]
+struct WatchedFiles {
+    loaded: HashMap<String, FileState>,
+    changed: HashMap<String, FileState>,
+}
+
+/// Snapshots of the instances of banks that were unloaded but failed to load again.
+type PendingSnapshots = HashMap<String, Vec<(Entity, InstanceSnapshot)>>;
+
+fn reload_changed_banks(
+    studio: Res<FmodStudio>,
+    mut progress: ResMut<BankLoadingProgress>,
+    mut audio_sources: Query<(Entity, &mut AudioSource)>,
+    mut files: Local<WatchedFiles>,
+    mut pending: Local<PendingSnapshots>,
+    queue: Res<CallbackQueue>,
+    mut loaded: MessageWriter<BankLoaded>,
+) {
+    let mut changed_banks = Vec::new();
+
+    for (path, bank) in progress.loaded_banks() {
+        let Some(state) = FileState::read(path) else {
+            continue;
+        };
+
+        let Some(loaded) = files.loaded.get(path) else {
+            files.loaded.insert(path.to_string(), state);
+            continue;
+        };
+
+        if *loaded == state {
+            files.changed.remove(path);
+            continue;
+        }
+
+        // FMOD Studio may still be writing the bank, so wait until it didn't change for a poll.
+        if files.changed.insert(path.to_string(), state) == Some(state) {
+            files.changed.remove(path);
+            changed_banks.push((path.to_string(), bank, state));
+        }
+    }
+
+    for (path, bank, state) in changed_banks {
+        info!("Bank {path} changed on disk, reloading");
+
+        // The bank is already unloaded if a previous reload attempt failed.
+        if bank.is_valid() {
+            let snapshots = match capture_instances(bank, &audio_sources) {
+                Ok(snapshots) => snapshots,
+                Err(e) => {
+                    warn!("Could not capture the event instances of bank {path}: {e}");
+                    continue;
+                }
+            };
+
+            if let Err(e) = bank.unload() {
+                warn!("Could not unload bank {path}: {e}");
+                continue;
+            }
+
+            pending.entry(path.clone()).or_default().extend(snapshots);
+
+            if let Err(e) = studio.flush_commands() {
+                warn!("Could not flush FMOD commands after unloading bank {path}: {e}");
+            }
+        }
+
+        let new_bank = match studio.load_bank(&path, FMOD_STUDIO_LOAD_BANK_NORMAL) {
+            Ok(new_bank) => new_bank,
+            Err(e) => {
+                // The bank is retried once the file changes or settles again, and the snapshots
+                // are kept until then.
+                warn!("Could not reload bank {path}: {e}");
+                continue;
+            }
+        };
+
+        files.loaded.insert(path.clone(), state);
+        progress.replace_bank(&path, new_bank);
+        loaded.write(BankLoaded {
+            path: path.clone(),
+            bank: new_bank,
+        });
+
+        for (entity, snapshot) in pending.remove(&path).unwrap_or_default() {
+            let Ok((_, mut audio_source)) = audio_sources.get_mut(entity) else {
+                continue;
+            };
+
+            match snapshot.restore(&studio, &queue) {
+                Ok(event_instance) => audio_source.event_instance = event_instance,
+                Err(e) => warn!("Could not recreate event instance after reloading {path}: {e}"),
+            }
+        }
+    }
+}
+
+/// Captures the state of every audio source playing an event of the given bank.
+fn capture_instances(
+    bank: Bank,
+    audio_sources: &Query<(Entity, &mut AudioSource)>,
+) -> crate::Result<Vec<(Entity, InstanceSnapshot)>> {
+    let event_ids = bank
+        .get_event_list(bank.get_event_count()?)?
+        .iter()
+        .map(|description| description.get_id())
+        .collect::<std::result::Result<Vec<Guid>, _>>()?;
+
+    let mut snapshots = Vec::new();
+
+    for (entity, audio_source) in audio_sources.iter() {
+        if !audio_source.is_valid() {
+            continue;
+        }
+
+        let event_id = audio_source.get_description()?.get_id()?;
+
+        if event_ids.iter().any(|id| guids_equal(id, &event_id)) {
+            snapshots.push((
+                entity,
+                InstanceSnapshot::capture(audio_source.event_instance)?,
+            ));
+        }
+    }
+
+    Ok(snapshots)
+}
+
+/// The state of an event instance that is restored after its bank was reloaded.
+struct InstanceSnapshot {
+    event_id: Guid,
+    playback_state: PlaybackState,
+    paused: bool,
+    timeline_position: i32,
+    volume: f32,
+    attributes: Attributes3d,
+    parameters: Vec<(String, f32)>,
+}
+
+impl InstanceSnapshot {
+    fn capture(event_instance: EventInstance) -> crate::Result<Self> {
+        let description = event_instance.get_description()?;
+
+        let mut parameters = Vec::new();
+
+        for index in 0..description.get_parameter_description_count()? {
+            let parameter = description.get_parameter_description_by_index(index)?;
+
+            // Automatic and read-only parameters are driven by FMOD itself.
+            if parameter.flags & (FMOD_STUDIO_PARAMETER_AUTOMATIC | FMOD_STUDIO_PARAMETER_READONLY)
+                != 0
+            {
+                continue;
+            }
+
+            let (value, _) = event_instance.get_parameter_by_id(parameter.id)?;
+            parameters.push((parameter.name, value));
+        }
+
+        Ok(InstanceSnapshot {
+            event_id: description.get_id()?,
+            playback_state: event_instance.get_playback_state()?,
+            paused: event_instance.get_paused()?,
+            timeline_position: event_instance.get_timeline_position()?,
+            volume: event_instance.get_volume()?.0,
+            attributes: event_instance.get_3d_attributes()?,
+            parameters,
+        })
+    }
+
+    fn restore(&self, studio: &FmodStudio, queue: &CallbackQueue) -> crate::Result<EventInstance> {
+        let event_instance = studio
+            .get_event_by_id(self.event_id.clone())?
+            .create_instance()?;
+        register_callback(event_instance, queue)?;
+
+        for (name, value) in &self.parameters {
+            // Parameters may have been removed or made read-only in the new build.
+            if let Err(e) = event_instance.set_parameter_by_name(name, *value, true) {
+                warn!("Could not restore parameter {name}: {e}");
+            }
+        }
+
+        event_instance.set_volume(self.volume)?;
+        event_instance.set_3d_attributes(self.attributes.clone())?;
+
+        if matches!(
+            self.playback_state,
+            PlaybackState::Playing | PlaybackState::Starting | PlaybackState::Sustaining
+        ) {
+            event_instance.start()?;
+            event_instance.set_timeline_position(self.timeline_position)?;
+        }
+
+        event_instance.set_paused(self.paused)?;
+
+        Ok(event_instance)
+    }
+}
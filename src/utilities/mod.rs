@@ -3,7 +3,13 @@
 //! Collection of useful plugins, components or systems that are not part of the FMOD API but help
 //! when developing bevy games with FMOD.
 
+mod hot_reload;
 mod mute_when_unfocused;
+mod offline_render;
 
+#[doc(inline)]
+pub use hot_reload::BankHotReloadPlugin;
 #[doc(inline)]
 pub use mute_when_unfocused::MuteWhenUnfocusedPlugin;
+#[doc(inline)]
+pub use offline_render::{OfflineRenderer, Wav, WavDifference, WavTolerance};
@@ -1,7 +1,8 @@
 use crate::FmodStudio;
+use crate::fmod_status::fmod_enabled;
 use bevy::app::{App, Plugin, Update};
 use bevy::ecs::error::Result;
-use bevy::prelude::{Entity, Local, MessageReader, ResMut, Single, With};
+use bevy::prelude::{Entity, IntoScheduleConfigs, Local, MessageReader, ResMut, Single, With};
 use bevy::window::{PrimaryWindow, WindowFocused};
 
 /// When this plugin is added, the audio will be muted when the [PrimaryWindow] is not focused
@@ -10,7 +11,7 @@ pub struct MuteWhenUnfocusedPlugin;
 
 impl Plugin for MuteWhenUnfocusedPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, mute_when_unfocused);
+        app.add_systems(Update, mute_when_unfocused.run_if(fmod_enabled));
     }
 }
 
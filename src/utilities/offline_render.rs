@@ -0,0 +1,461 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::prelude::{App, Entity, With};
+use bevy::time::TimeUpdateStrategy;
+use libfmod::SpeakerMode;
+
+use crate::components::audio_source::AudioSource;
+use crate::error::Error;
+use crate::fmod_settings::{FmodSettings, Output};
+use crate::fmod_status::FmodStatus;
+use crate::{FmodPlugin, FmodStudio};
+
+const SAMPLE_RATE: i32 = 48_000;
+const DSP_BUFFER_LENGTH: u32 = 1024;
+const DSP_NUM_BUFFERS: i32 = 4;
+
+/// Renders the audio of an [`App`] to a WAV file without playing it, for golden-file regression
+/// tests.
+///
+/// FMOD is initialized with the non-realtime WAV writer, and the app is updated for a fixed number
+/// of simulated frames. Every frame advances [`Time`](bevy::time::Time) by exactly one DSP buffer,
+/// so the result is the same on every run and every machine.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_fmod::utilities::{OfflineRenderer, Wav, WavTolerance};
+/// let renderer = OfflineRenderer::new(
+///     &["./assets/audio/Master.bank", "./assets/audio/Master.strings.bank"],
+///     "./target/forest.wav",
+/// )
+/// .with_frames(200);
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, renderer.plugin()));
+/// // Spawn and start your audio sources ...
+///
+/// let rendered = renderer.render(app).unwrap();
+/// let reference = Wav::read("./tests/golden/forest.wav").unwrap();
+/// let difference = rendered.difference(&reference).unwrap();
+///
+/// assert!(difference.is_within(&WavTolerance::default()), "{difference:?}");
+/// ```
+pub struct OfflineRenderer {
+    audio_banks_paths: &'static [&'static str],
+    output_path: PathBuf,
+    frames: u32,
+}
+
+impl OfflineRenderer {
+    /// Creates a renderer which loads the given banks and writes the mix to `output_path`.
+    #[must_use]
+    pub fn new(
+        audio_banks_paths: &'static [&'static str],
+        output_path: impl Into<PathBuf>,
+    ) -> Self {
+        OfflineRenderer {
+            audio_banks_paths,
+            output_path: output_path.into(),
+            frames: 60,
+        }
+    }
+
+    /// Sets the number of simulated frames to render. Defaults to `60`.
+    #[must_use]
+    pub fn with_frames(mut self, frames: u32) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// The simulated duration of a single frame, which is the length of one DSP buffer.
+    pub fn frame_time(&self) -> Duration {
+        Duration::from_secs_f64(f64::from(DSP_BUFFER_LENGTH) / f64::from(SAMPLE_RATE))
+    }
+
+    /// Returns an [`FmodPlugin`] which writes the mix to the output path of this renderer.
+    ///
+    /// Add it to the app instead of a regular [`FmodPlugin`].
+    pub fn plugin(&self) -> FmodPlugin {
+        FmodPlugin::new(self.audio_banks_paths).with_settings(
+            FmodSettings::default()
+                .with_output(Output::WavWriterNrt(self.output_path.clone()))
+                .with_software_format(SAMPLE_RATE, SpeakerMode::Stereo)
+                .with_dsp_buffer_size(DSP_BUFFER_LENGTH, DSP_NUM_BUFFERS),
+        )
+    }
+
+    /// Updates the app for the configured number of frames and returns the rendered audio.
+    ///
+    /// All audio sources are despawned and FMOD is released afterwards, which finishes writing
+    /// the WAV file. The app is consumed, as it can't play audio anymore.
+    pub fn render(&self, mut app: App) -> crate::Result<Wav> {
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(self.frame_time()));
+
+        for _ in 0..self.frames {
+            app.update();
+        }
+
+        let world = app.world_mut();
+
+        let audio_sources: Vec<Entity> = world
+            .query_filtered::<Entity, With<AudioSource>>()
+            .iter(world)
+            .collect();
+
+        audio_sources.into_iter().for_each(|entity| {
+            world.despawn(entity);
+        });
+
+        if let Some(studio) = world.remove_resource::<FmodStudio>() {
+            world.insert_resource(FmodStatus::Disabled {
+                reason: "FMOD was released after rendering".to_string(),
+            });
+            studio.release()?;
+        }
+
+        Wav::read(&self.output_path)
+    }
+}
+
+/// Audio samples read from a WAV file, converted to `f32` and interleaved by channel.
+#[derive(Clone, Debug)]
+pub struct Wav {
+    /// The sample rate in Hz.
+    pub sample_rate: u32,
+    /// The number of interleaved channels.
+    pub channels: u16,
+    /// The interleaved samples in the range `-1.0..=1.0`.
+    pub samples: Vec<f32>,
+}
+
+impl Wav {
+    /// Reads a 16 bit PCM or 32 bit float WAV file.
+    pub fn read(path: impl AsRef<Path>) -> crate::Result<Self> {
+        Wav::parse(&fs::read(path)?)
+    }
+
+    fn parse(bytes: &[u8]) -> crate::Result<Self> {
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(Error::InvalidWav("missing RIFF/WAVE header"));
+        }
+
+        let mut format = None;
+        let mut data = None;
+        let mut offset = 12;
+
+        while offset + 8 <= bytes.len() {
+            let id = &bytes[offset..offset + 4];
+            let size = read_u32(bytes, offset + 4) as usize;
+            let start = offset + 8;
+            // The writer may not have patched the chunk size, so clamp it to the file length.
+            let end = start.saturating_add(size).min(bytes.len());
+
+            match id {
+                b"fmt " => format = Some(WavFormat::parse(&bytes[start..end])?),
+                b"data" => data = Some(&bytes[start..end]),
+                _ => {}
+            }
+
+            offset = end + (size & 1);
+        }
+
+        let format = format.ok_or(Error::InvalidWav("missing fmt chunk"))?;
+        let data = data.ok_or(Error::InvalidWav("missing data chunk"))?;
+
+        let samples = match (format.encoding, format.bits_per_sample) {
+            (WAVE_FORMAT_PCM, 16) => data
+                .chunks_exact(2)
+                .map(|sample| f32::from(i16::from_le_bytes([sample[0], sample[1]])) / 32768.0)
+                .collect(),
+            (WAVE_FORMAT_IEEE_FLOAT, 32) => data
+                .chunks_exact(4)
+                .map(|sample| f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]))
+                .collect(),
+            _ => return Err(Error::InvalidWav("unsupported sample format")),
+        };
+
+        Ok(Wav {
+            sample_rate: format.sample_rate,
+            channels: format.channels,
+            samples,
+        })
+    }
+
+    /// Writes the samples as a 32 bit float WAV file, e.g. to update a reference file.
+    pub fn write(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        fs::write(path, self.to_bytes()?)?;
+
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+        // The RIFF chunk size includes the 36 bytes of headers after it.
+        let data_size = self
+            .samples
+            .len()
+            .checked_mul(4)
+            .and_then(|size| u32::try_from(size).ok())
+            .filter(|size| size.checked_add(36).is_some())
+            .ok_or(Error::InvalidWav("too many samples for a WAV file"))?;
+        let block_align = self.channels * 4;
+
+        let mut bytes = Vec::with_capacity(44 + data_size as usize);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&WAVE_FORMAT_IEEE_FLOAT.to_le_bytes());
+        bytes.extend_from_slice(&self.channels.to_le_bytes());
+        bytes.extend_from_slice(&self.sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(self.sample_rate * u32::from(block_align)).to_le_bytes());
+        bytes.extend_from_slice(&block_align.to_le_bytes());
+        bytes.extend_from_slice(&32u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+        self.samples
+            .iter()
+            .for_each(|sample| bytes.extend_from_slice(&sample.to_le_bytes()));
+
+        Ok(bytes)
+    }
+
+    /// The length of the audio.
+    pub fn duration(&self) -> Duration {
+        let frames = self.samples.len() / usize::from(self.channels.max(1));
+        Duration::from_secs_f64(frames as f64 / f64::from(self.sample_rate.max(1)))
+    }
+
+    /// Compares the samples with another WAV. Missing samples of the shorter one count as silence.
+    ///
+    /// Returns an error if the sample rate or channel count differ.
+    pub fn difference(&self, other: &Wav) -> crate::Result<WavDifference> {
+        if self.sample_rate != other.sample_rate || self.channels != other.channels {
+            return Err(Error::InvalidWav("sample rate or channel count differ"));
+        }
+
+        let length = self.samples.len().max(other.samples.len());
+
+        if length == 0 {
+            return Ok(WavDifference::default());
+        }
+
+        let (sum_of_squares, peak) = (0..length)
+            .map(|i| {
+                let a = self.samples.get(i).copied().unwrap_or_default();
+                let b = other.samples.get(i).copied().unwrap_or_default();
+                (a - b).abs()
+            })
+            .fold((0.0f64, 0.0f32), |(sum, peak), difference| {
+                (
+                    sum + f64::from(difference) * f64::from(difference),
+                    peak.max(difference),
+                )
+            });
+
+        Ok(WavDifference {
+            rms: (sum_of_squares / length as f64).sqrt() as f32,
+            peak,
+        })
+    }
+}
+
+/// The difference between two [`Wav`]s. See [`Wav::difference`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WavDifference {
+    /// The root mean square of the sample differences.
+    pub rms: f32,
+    /// The largest absolute sample difference.
+    pub peak: f32,
+}
+
+impl WavDifference {
+    /// Whether the difference is within the given tolerance.
+    pub fn is_within(&self, tolerance: &WavTolerance) -> bool {
+        self.rms <= tolerance.rms && self.peak <= tolerance.peak
+    }
+}
+
+/// The maximum [`WavDifference`] at which two renders are considered equal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WavTolerance {
+    /// The maximum root mean square of the sample differences.
+    pub rms: f32,
+    /// The maximum absolute sample difference.
+    pub peak: f32,
+}
+
+impl Default for WavTolerance {
+    /// Allows for small differences such as dithering or resampler noise.
+    fn default() -> Self {
+        WavTolerance {
+            rms: 0.001,
+            peak: 0.01,
+        }
+    }
+}
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+struct WavFormat {
+    encoding: u16,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+}
+
+impl WavFormat {
+    fn parse(chunk: &[u8]) -> crate::Result<Self> {
+        if chunk.len() < 16 {
+            return Err(Error::InvalidWav("fmt chunk too short"));
+        }
+
+        let mut encoding = read_u16(chunk, 0);
+
+        // The actual encoding is stored in the first two bytes of the sub format GUID.
+        if encoding == WAVE_FORMAT_EXTENSIBLE {
+            if chunk.len() < 26 {
+                return Err(Error::InvalidWav("fmt chunk too short"));
+            }
+            encoding = read_u16(chunk, 24);
+        }
+
+        Ok(WavFormat {
+            encoding,
+            channels: read_u16(chunk, 2),
+            sample_rate: read_u32(chunk, 4),
+            bits_per_sample: read_u16(chunk, 14),
+        })
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(samples: &[f32]) -> Wav {
+        Wav {
+            sample_rate: 48_000,
+            channels: 2,
+            samples: samples.to_vec(),
+        }
+    }
+
+    /// A 16 bit PCM file as written by most tools, with an extra chunk before the data.
+    fn pcm_bytes(samples: &[i16]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&WAVE_FORMAT_PCM.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&44_100u32.to_le_bytes());
+        bytes.extend_from_slice(&88_200u32.to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"LIST");
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0, 0, 0]);
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&(samples.len() as u32 * 2).to_le_bytes());
+        samples
+            .iter()
+            .for_each(|sample| bytes.extend_from_slice(&sample.to_le_bytes()));
+        bytes
+    }
+
+    #[test]
+    fn float_wav_round_trips() -> crate::Result<()> {
+        let original = wav(&[0.0, 0.5, -0.25, 1.0]);
+        let parsed = Wav::parse(&original.to_bytes()?)?;
+
+        assert_eq!(parsed.sample_rate, 48_000);
+        assert_eq!(parsed.channels, 2);
+        assert_eq!(parsed.samples, original.samples);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_pcm_and_skips_odd_sized_chunks() -> crate::Result<()> {
+        let parsed = Wav::parse(&pcm_bytes(&[0, 16384, -32768]))?;
+
+        assert_eq!(parsed.sample_rate, 44_100);
+        assert_eq!(parsed.channels, 1);
+        assert_eq!(parsed.samples, vec![0.0, 0.5, -1.0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn clamps_unpatched_data_size() -> crate::Result<()> {
+        let mut bytes = wav(&[0.5, 0.5]).to_bytes()?;
+        // A writer that was interrupted leaves the data size unpatched and the file cut short.
+        bytes[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+        bytes.truncate(48);
+
+        assert_eq!(Wav::parse(&bytes)?.samples, vec![0.5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(Wav::parse(b"RIFF\0\0\0\0AVI ").is_err());
+
+        let mut bytes = pcm_bytes(&[0]);
+        // Change the bits per sample to 24.
+        bytes[34] = 24;
+        assert!(Wav::parse(&bytes).is_err());
+    }
+
+    #[test]
+    fn difference_counts_missing_samples_as_silence() -> crate::Result<()> {
+        let difference = wav(&[0.5, 0.5]).difference(&wav(&[0.5]))?;
+
+        assert_eq!(difference.peak, 0.5);
+        assert!((difference.rms - (0.125f32).sqrt()).abs() < 1e-6);
+        assert!(!difference.is_within(&WavTolerance::default()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn identical_wavs_are_within_tolerance() -> crate::Result<()> {
+        let a = wav(&[0.1, -0.2, 0.3, -0.4]);
+        let difference = a.difference(&a)?;
+
+        assert_eq!(difference, WavDifference::default());
+        assert!(difference.is_within(&WavTolerance::default()));
+        assert_eq!(wav(&[]).difference(&wav(&[]))?, WavDifference::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn difference_requires_matching_format() {
+        let mut mono = wav(&[0.0]);
+        mono.channels = 1;
+
+        assert!(wav(&[0.0]).difference(&mono).is_err());
+    }
+}
@@ -0,0 +1,607 @@
+//! Validating the events and parameters used by the game against the loaded banks.
+//!
+//! [`AudioReferences`] lists the event paths and parameter names the game uses, and
+//! [`AudioReferences::validate`] checks them against the banks loaded in [`FmodStudio`]. The
+//! references can be added by hand, e.g. from a manifest with [`AudioReferences::from_events`], or
+//! recorded while the game runs: as long as the resource exists, the path of every [`FmodEvent`]
+//! and [`AudioSource`], its local parameters set through [`FmodParameters`] and the global
+//! parameters set through [`GlobalParameters`] are added to it, including the range of values
+//! they were set to. This covers values written by
+//! [`ParameterTweens`](crate::components::parameter_tweens::ParameterTweens) and
+//! [`ParameterBindings`](crate::components::parameter_bindings::ParameterBindings), as they are
+//! recorded after both ran.
+//!
+//! This makes broken audio references fail CI instead of failing silently in the game:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_fmod::prelude::*;
+//! let mut app = App::new();
+//! app.add_plugins((
+//!     MinimalPlugins,
+//!     FmodPlugin::new(&["./assets/audio/Master.bank", "./assets/audio/Master.strings.bank"])
+//!         .with_settings(FmodSettings {
+//!             output: bevy_fmod::Output::NoSound,
+//!             ..default()
+//!         }),
+//! ))
+//! .insert_resource(
+//!     AudioReferences::new()
+//!         .with_event("event:/Ambience/Forest")
+//!         .with_parameter("event:/Vehicles/Car Engine", "RPM", Some(8000.0))
+//!         .with_global_parameter("Weather", None),
+//! );
+//!
+//! // Run the game or a scripted level to record further references ...
+//! app.update();
+//!
+//! let report = app
+//!     .world()
+//!     .resource::<AudioReferences>()
+//!     .validate(app.world().resource::<FmodStudio>());
+//!
+//! assert!(report.is_ok(), "{report}");
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+use bevy::ecs::error::Result;
+use bevy::ecs::lifecycle::Insert;
+use bevy::ecs::observer::On;
+use bevy::prelude::{Changed, DetectChanges, Or, Query, Res, ResMut, Resource};
+use libfmod::ParameterDescription;
+
+use crate::components::audio_source::AudioSource;
+use crate::components::fmod_event::FmodEvent;
+use crate::components::fmod_parameters::{FmodParameters, ParameterKey};
+use crate::fmod_studio::FmodStudio;
+use crate::global_parameters::GlobalParameters;
+
+/// The events and parameters used by the game. See the [module documentation](self).
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AudioReferences {
+    events: Vec<EventReference>,
+    global_parameters: Vec<ParameterReference>,
+}
+
+#[derive(Debug, Clone)]
+struct EventReference {
+    path: String,
+    parameters: Vec<ParameterReference>,
+}
+
+#[derive(Debug, Clone)]
+struct ParameterReference {
+    name: String,
+    /// The smallest and largest value the parameter is set to, if any.
+    values: Option<RangeInclusive<f32>>,
+}
+
+/// The result of [`AudioReferences::validate`].
+///
+/// Its [`Display`] implementation lists one issue per line, for assertion messages.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+/// A reference that does not match the loaded banks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// The event does not exist in the loaded banks.
+    MissingEvent {
+        /// The path of the event.
+        path: String,
+    },
+    /// The event, or the banks for global parameters, have no parameter with this name.
+    MissingParameter {
+        /// The path of the event, or `None` for global parameters.
+        event: Option<String>,
+        /// The name of the parameter.
+        name: String,
+    },
+    /// The parameter is set to a value outside of its range.
+    OutOfRange {
+        /// The path of the event, or `None` for global parameters.
+        event: Option<String>,
+        /// The name of the parameter.
+        name: String,
+        /// The value outside of the range.
+        value: f32,
+        /// The smallest value of the parameter.
+        minimum: f32,
+        /// The largest value of the parameter.
+        maximum: f32,
+    },
+}
+
+impl ParameterReference {
+    fn new(name: &str, value: Option<f32>) -> Self {
+        ParameterReference {
+            name: name.to_string(),
+            values: value.map(|value| value..=value),
+        }
+    }
+
+    fn add_value(&mut self, value: f32) {
+        self.values = Some(match &self.values {
+            Some(values) => values.start().min(value)..=values.end().max(value),
+            None => value..=value,
+        });
+    }
+
+    fn validate(
+        &self,
+        event: Option<&str>,
+        description: Option<ParameterDescription>,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        let Some(description) = description else {
+            issues.push(ValidationIssue::MissingParameter {
+                event: event.map(str::to_string),
+                name: self.name.clone(),
+            });
+            return;
+        };
+
+        let Some(values) = &self.values else {
+            return;
+        };
+
+        for value in [*values.start(), *values.end()] {
+            if !(description.minimum..=description.maximum).contains(&value) {
+                issues.push(ValidationIssue::OutOfRange {
+                    event: event.map(str::to_string),
+                    name: self.name.clone(),
+                    value,
+                    minimum: description.minimum,
+                    maximum: description.maximum,
+                });
+            }
+
+            // Don't report the same value twice.
+            if values.start() == values.end() {
+                break;
+            }
+        }
+    }
+}
+
+/// Adds the parameter or the value to an existing reference.
+fn add_parameter(parameters: &mut Vec<ParameterReference>, name: &str, value: Option<f32>) {
+    match parameters
+        .iter_mut()
+        .find(|parameter| parameter.name == name)
+    {
+        Some(parameter) => {
+            if let Some(value) = value {
+                parameter.add_value(value);
+            }
+        }
+        None => parameters.push(ParameterReference::new(name, value)),
+    }
+}
+
+/// References need to be recorded when an event or its parameters change.
+type ReferencesChanged = Or<(Changed<FmodEvent>, Changed<FmodParameters>)>;
+
+impl AudioReferences {
+    /// Creates an empty list of references.
+    pub fn new() -> Self {
+        AudioReferences::default()
+    }
+
+    /// Creates references to the given events, e.g. all events of the
+    /// [generated paths](crate::studio_paths) or a list of event paths read from a manifest.
+    pub fn from_events<S: Into<String>>(events: impl IntoIterator<Item = S>) -> Self {
+        let mut references = AudioReferences::new();
+
+        for path in events {
+            references.add_event(path);
+        }
+
+        references
+    }
+
+    /// Adds an event by its path.
+    #[must_use]
+    pub fn with_event(mut self, path: impl Into<String>) -> Self {
+        self.add_event(path);
+        self
+    }
+
+    /// Adds a local parameter of an event, optionally with a value it is set to.
+    #[must_use]
+    pub fn with_parameter(
+        mut self,
+        event: impl Into<String>,
+        name: impl Into<String>,
+        value: Option<f32>,
+    ) -> Self {
+        self.add_parameter(event, name, value);
+        self
+    }
+
+    /// Adds a global parameter, optionally with a value it is set to.
+    #[must_use]
+    pub fn with_global_parameter(mut self, name: impl Into<String>, value: Option<f32>) -> Self {
+        self.add_global_parameter(name, value);
+        self
+    }
+
+    /// Adds an event by its path.
+    pub fn add_event(&mut self, path: impl Into<String>) {
+        self.event_mut(path.into());
+    }
+
+    /// Adds a local parameter of an event, optionally with a value it is set to.
+    pub fn add_parameter(
+        &mut self,
+        event: impl Into<String>,
+        name: impl Into<String>,
+        value: Option<f32>,
+    ) {
+        let event = self.event_mut(event.into());
+        add_parameter(&mut event.parameters, &name.into(), value);
+    }
+
+    /// Adds a global parameter, optionally with a value it is set to.
+    pub fn add_global_parameter(&mut self, name: impl Into<String>, value: Option<f32>) {
+        add_parameter(&mut self.global_parameters, &name.into(), value);
+    }
+
+    /// The paths of the referenced events.
+    pub fn events(&self) -> impl Iterator<Item = &str> {
+        self.events.iter().map(|event| event.path.as_str())
+    }
+
+    fn event_mut(&mut self, path: String) -> &mut EventReference {
+        let index = match self.events.iter().position(|event| event.path == path) {
+            Some(index) => index,
+            None => {
+                self.events.push(EventReference {
+                    path,
+                    parameters: Vec::new(),
+                });
+                self.events.len() - 1
+            }
+        };
+
+        &mut self.events[index]
+    }
+
+    /// Checks all references against the banks loaded in the studio. Event paths can only be
+    /// found if the strings bank is loaded.
+    pub fn validate(&self, studio: &FmodStudio) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        for event in &self.events {
+            let Ok(description) = studio.get_event(&event.path) else {
+                issues.push(ValidationIssue::MissingEvent {
+                    path: event.path.clone(),
+                });
+                continue;
+            };
+
+            for parameter in &event.parameters {
+                parameter.validate(
+                    Some(&event.path),
+                    description
+                        .get_parameter_description_by_name(&parameter.name)
+                        .ok(),
+                    &mut issues,
+                );
+            }
+        }
+
+        for parameter in &self.global_parameters {
+            parameter.validate(
+                None,
+                studio
+                    .get_parameter_description_by_name(&parameter.name)
+                    .ok(),
+                &mut issues,
+            );
+        }
+
+        ValidationReport { issues }
+    }
+
+    /// Records the event of every inserted [`AudioSource`], including those not spawned through
+    /// an [`FmodEvent`].
+    pub(crate) fn on_insert_audio_source(
+        insert: On<Insert, AudioSource>,
+        query: Query<&AudioSource>,
+        references: Option<ResMut<AudioReferences>>,
+    ) -> Result {
+        let Some(mut references) = references else {
+            return Ok(());
+        };
+
+        let audio_source = query.get(insert.entity)?;
+
+        if !audio_source.is_valid() {
+            return Ok(());
+        }
+
+        // Without the strings bank, the event has no path to record.
+        if let Ok(path) = audio_source.get_description()?.get_path() {
+            references.add_event(path);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn record(
+        query: Query<(&FmodEvent, Option<&FmodParameters>), ReferencesChanged>,
+        global_parameters: Res<GlobalParameters>,
+        mut references: ResMut<AudioReferences>,
+    ) {
+        for (event, parameters) in query.iter() {
+            references.add_event(event.path.as_str());
+
+            for (key, value) in parameters.iter().flat_map(|parameters| parameters.iter()) {
+                // Parameters set by ID were looked up from the event and can't be misspelled.
+                if let ParameterKey::Name(name) = key {
+                    references.add_parameter(event.path.as_str(), name.as_str(), Some(value));
+                }
+            }
+        }
+
+        // Pending values are only there until they are sent to FMOD at the end of the frame.
+        if global_parameters.is_changed() {
+            for (name, value) in global_parameters.pending_values() {
+                references.add_global_parameter(name, value);
+            }
+        }
+    }
+}
+
+impl ValidationReport {
+    /// Returns `true` if all references match the loaded banks.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// The references that don't match the loaded banks.
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_ok() {
+            return write!(f, "All audio references are valid");
+        }
+
+        writeln!(f, "{} invalid audio references:", self.issues.len())?;
+        for issue in &self.issues {
+            writeln!(f, "- {issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::MissingEvent { path } => write!(f, "Missing event {path}"),
+            ValidationIssue::MissingParameter {
+                event: Some(event),
+                name,
+            } => write!(f, "Missing parameter {name} of {event}"),
+            ValidationIssue::MissingParameter { event: None, name } => {
+                write!(f, "Missing global parameter {name}")
+            }
+            ValidationIssue::OutOfRange {
+                event,
+                name,
+                value,
+                minimum,
+                maximum,
+            } => {
+                let owner = event.as_deref().unwrap_or("the global parameters");
+                write!(
+                    f,
+                    "Parameter {name} of {owner} is set to {value}, outside of {minimum}..={maximum}"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::{RunSystemError, RunSystemOnce};
+    use bevy::prelude::World;
+    use libfmod::{Guid, ParameterId, ParameterType};
+
+    use super::*;
+
+    fn description(minimum: f32, maximum: f32) -> ParameterDescription {
+        ParameterDescription {
+            name: "RPM".to_string(),
+            id: ParameterId {
+                data_1: 1,
+                data_2: 2,
+            },
+            minimum,
+            maximum,
+            defaultvalue: minimum,
+            type_: ParameterType::GameControlled,
+            flags: 0,
+            guid: Guid {
+                data_1: 0,
+                data_2: 0,
+                data_3: 0,
+                data_4: [0; 8],
+            },
+        }
+    }
+
+    fn issues(parameter: &ParameterReference, minimum: f32, maximum: f32) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        parameter.validate(
+            Some("event:/Car"),
+            Some(description(minimum, maximum)),
+            &mut issues,
+        );
+        issues
+    }
+
+    fn out_of_range(value: f32) -> ValidationIssue {
+        ValidationIssue::OutOfRange {
+            event: Some("event:/Car".to_string()),
+            name: "RPM".to_string(),
+            value,
+            minimum: 0.0,
+            maximum: 8000.0,
+        }
+    }
+
+    #[test]
+    fn add_value_widens_the_range() {
+        let mut parameter = ParameterReference::new("RPM", None);
+        assert_eq!(parameter.values, None);
+
+        parameter.add_value(1000.0);
+        assert_eq!(parameter.values, Some(1000.0..=1000.0));
+
+        parameter.add_value(500.0);
+        parameter.add_value(3000.0);
+        parameter.add_value(2000.0);
+        assert_eq!(parameter.values, Some(500.0..=3000.0));
+    }
+
+    #[test]
+    fn reports_values_outside_of_the_range() {
+        let mut parameter = ParameterReference::new("RPM", Some(-100.0));
+        assert_eq!(issues(&parameter, 0.0, 8000.0), [out_of_range(-100.0)]);
+
+        parameter.add_value(9000.0);
+        assert_eq!(
+            issues(&parameter, 0.0, 8000.0),
+            [out_of_range(-100.0), out_of_range(9000.0)]
+        );
+
+        let parameter = ParameterReference::new("RPM", Some(9000.0));
+        assert_eq!(issues(&parameter, 0.0, 8000.0), [out_of_range(9000.0)]);
+    }
+
+    #[test]
+    fn accepts_values_inside_of_the_range() {
+        let mut parameter = ParameterReference::new("RPM", Some(0.0));
+        parameter.add_value(8000.0);
+        assert!(issues(&parameter, 0.0, 8000.0).is_empty());
+
+        let parameter = ParameterReference::new("RPM", None);
+        assert!(issues(&parameter, 0.0, 8000.0).is_empty());
+    }
+
+    #[test]
+    fn reports_missing_parameters() {
+        let mut issues = Vec::new();
+        ParameterReference::new("Weather", Some(1.0)).validate(None, None, &mut issues);
+
+        assert_eq!(
+            issues,
+            [ValidationIssue::MissingParameter {
+                event: None,
+                name: "Weather".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn report_lists_one_issue_per_line() {
+        let report = ValidationReport::default();
+        assert!(report.is_ok());
+        assert_eq!(report.to_string(), "All audio references are valid");
+
+        let report = ValidationReport {
+            issues: vec![
+                ValidationIssue::MissingEvent {
+                    path: "event:/Car".to_string(),
+                },
+                ValidationIssue::MissingParameter {
+                    event: None,
+                    name: "Weather".to_string(),
+                },
+                out_of_range(9000.0),
+            ],
+        };
+        assert!(!report.is_ok());
+        assert_eq!(report.issues().len(), 3);
+        assert_eq!(
+            report.to_string(),
+            "3 invalid audio references:\n\
+             - Missing event event:/Car\n\
+             - Missing global parameter Weather\n\
+             - Parameter RPM of event:/Car is set to 9000, outside of 0..=8000\n"
+        );
+    }
+
+    #[test]
+    fn events_are_added_once() {
+        let references = AudioReferences::from_events(["event:/Car", "event:/Rain", "event:/Car"])
+            .with_parameter("event:/Car", "RPM", None);
+
+        assert_eq!(
+            references.events().collect::<Vec<_>>(),
+            ["event:/Car", "event:/Rain"]
+        );
+        assert_eq!(references.events[0].parameters.len(), 1);
+    }
+
+    #[test]
+    fn records_events_and_parameters() -> std::result::Result<(), RunSystemError> {
+        let mut world = World::new();
+        world.init_resource::<AudioReferences>();
+        world.init_resource::<GlobalParameters>();
+
+        world.spawn((
+            FmodEvent::new("event:/Car"),
+            FmodParameters::new()
+                .with_parameter("RPM", 1000.0)
+                .with_parameter(
+                    ParameterId {
+                        data_1: 1,
+                        data_2: 2,
+                    },
+                    0.5,
+                ),
+        ));
+        world.resource_mut::<GlobalParameters>().set("Weather", 0.5);
+        world
+            .resource_mut::<GlobalParameters>()
+            .set_label("Location", "Cave");
+
+        world.run_system_once(AudioReferences::record)?;
+
+        let mut car = world.spawn(FmodEvent::new("event:/Car"));
+        car.insert(FmodParameters::new().with_parameter("RPM", 9000.0));
+        world.resource_mut::<GlobalParameters>().set("Weather", 2.0);
+
+        world.run_system_once(AudioReferences::record)?;
+
+        let references = world.resource::<AudioReferences>();
+        assert_eq!(references.events().collect::<Vec<_>>(), ["event:/Car"]);
+
+        let parameters = &references.events[0].parameters;
+        assert_eq!(parameters.len(), 1, "parameters set by ID are not recorded");
+        assert_eq!(parameters[0].name, "RPM");
+        assert_eq!(parameters[0].values, Some(1000.0..=9000.0));
+
+        let globals = &references.global_parameters;
+        assert_eq!(globals.len(), 2);
+        assert_eq!(globals[0].name, "Weather");
+        assert_eq!(globals[0].values, Some(0.5..=2.0));
+        assert_eq!(globals[1].name, "Location");
+        assert_eq!(globals[1].values, None);
+
+        Ok(())
+    }
+}